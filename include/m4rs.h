@@ -0,0 +1,372 @@
+#ifndef M4RS_H
+#define M4RS_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Result code of FFI functions
+typedef enum M4rsStatus {
+  M4RS_STATUS_OK = 0,
+  M4RS_STATUS_NULL_POINTER = 1,
+  M4RS_STATUS_BUFFER_TOO_SMALL = 2,
+  // Stream has not got enough entries to produce a value yet
+  M4RS_STATUS_NOT_READY = 3,
+  // Pushed entry is older than the last one in the stream
+  M4RS_STATUS_OUT_OF_ORDER = 4,
+  M4RS_STATUS_CONTAINS_NAN = 10,
+  M4RS_STATUS_CONTAINS_INFINITE = 11,
+  M4RS_STATUS_LONG_DURATION_IS_NOT_GREATER_THAN_SHORT_DURATION = 12,
+  M4RS_STATUS_MUST_BE_POSITIVE_F32 = 13,
+  M4RS_STATUS_DIVIDED_BY_ZERO = 14,
+  M4RS_STATUS_UNKNOWN_INDICATOR = 15,
+  M4RS_STATUS_INVALID_SPEC = 16,
+  M4RS_STATUS_INVALID_PARAMETER = 17,
+  M4RS_STATUS_TOO_MANY_PARAMETERS = 18,
+  M4RS_STATUS_MUST_BE_POSITIVE = 19,
+  M4RS_STATUS_INVALID_SESSION = 20,
+} M4rsStatus;
+
+// Single value indicator computed by [`Stream`]
+typedef enum M4rsStreamKind {
+  M4RS_STREAM_KIND_SMA,
+  M4RS_STREAM_KIND_EMA,
+  M4RS_STREAM_KIND_WMA,
+  M4RS_STREAM_KIND_RMA,
+  M4RS_STREAM_KIND_DEMA,
+  M4RS_STREAM_KIND_TEMA,
+  M4RS_STREAM_KIND_HMA,
+  M4RS_STREAM_KIND_MOMENTUM,
+  M4RS_STREAM_KIND_RCI,
+  M4RS_STREAM_KIND_RSI,
+  M4RS_STREAM_KIND_STANDARD_DEVIATION,
+  M4RS_STREAM_KIND_ATR,
+  M4RS_STREAM_KIND_CCI,
+  M4RS_STREAM_KIND_VWMA,
+  M4RS_STREAM_KIND_WILLIAMS_PERCENT_R,
+} M4rsStreamKind;
+
+// Opaque handle accumulating candlesticks for a single value indicator
+typedef struct M4rsStream M4rsStream;
+
+// Simple index entry
+typedef struct M4rsIndexEntry {
+  uint64_t at;
+  double value;
+} M4rsIndexEntry;
+
+typedef struct M4rsEnvelopeEntry {
+  uint64_t at;
+  double basis;
+  double upper;
+  double lower;
+} M4rsEnvelopeEntry;
+
+typedef struct M4rsBollingerBandEntry {
+  uint64_t at;
+  double avg;
+  double sigma;
+} M4rsBollingerBandEntry;
+
+typedef struct M4rsMacdEntry {
+  uint64_t at;
+  double macd;
+  double signal;
+  double histogram;
+} M4rsMacdEntry;
+
+// Candlestick entry
+typedef struct M4rsCandlestick {
+  uint64_t at;
+  double open;
+  double high;
+  double low;
+  double close;
+  double volume;
+} M4rsCandlestick;
+
+typedef struct M4rsDmiEntry {
+  uint64_t at;
+  double plus_di;
+  double minus_di;
+  double dx;
+  double adx;
+} M4rsDmiEntry;
+
+typedef struct M4rsStochasticsEntry {
+  uint64_t at;
+  // %K
+  double k;
+  // %D
+  double d;
+} M4rsStochasticsEntry;
+
+typedef struct M4rsSlowStochasticsEntry {
+  uint64_t at;
+  // %K
+  double k;
+  // %D
+  double d;
+  // Slow%D
+  double sd;
+} M4rsSlowStochasticsEntry;
+
+typedef struct M4rsWilliamsFractalsEntry {
+  uint64_t at;
+  bool up;
+  bool down;
+} M4rsWilliamsFractalsEntry;
+
+// Ichimoku Kinko Hyo entry (NaN where a line has no value)
+typedef struct M4rsIchimokuPoint {
+  uint64_t at;
+  double conversion_line;
+  double base_line;
+  double leading_span_a;
+  double leading_span_b;
+  double lagging_span;
+} M4rsIchimokuPoint;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Computes SMA (Simple Moving Average)
+enum M4rsStatus m4rs_sma(const struct M4rsIndexEntry *entries,
+                         size_t len,
+                         size_t duration,
+                         struct M4rsIndexEntry *out,
+                         size_t out_cap,
+                         size_t *out_len);
+
+// Computes EMA (Exponential Moving Average)
+enum M4rsStatus m4rs_ema(const struct M4rsIndexEntry *entries,
+                         size_t len,
+                         size_t duration,
+                         struct M4rsIndexEntry *out,
+                         size_t out_cap,
+                         size_t *out_len);
+
+// Computes WMA (Weighted Moving Average)
+enum M4rsStatus m4rs_wma(const struct M4rsIndexEntry *entries,
+                         size_t len,
+                         size_t duration,
+                         struct M4rsIndexEntry *out,
+                         size_t out_cap,
+                         size_t *out_len);
+
+// Computes RMA (Running Moving Average)
+enum M4rsStatus m4rs_rma(const struct M4rsIndexEntry *entries,
+                         size_t len,
+                         size_t duration,
+                         struct M4rsIndexEntry *out,
+                         size_t out_cap,
+                         size_t *out_len);
+
+// Computes DEMA (Double Exponential Moving Average)
+enum M4rsStatus m4rs_dema(const struct M4rsIndexEntry *entries,
+                          size_t len,
+                          size_t duration,
+                          struct M4rsIndexEntry *out,
+                          size_t out_cap,
+                          size_t *out_len);
+
+// Computes TEMA (Triple Exponential Moving Average)
+enum M4rsStatus m4rs_tema(const struct M4rsIndexEntry *entries,
+                          size_t len,
+                          size_t duration,
+                          struct M4rsIndexEntry *out,
+                          size_t out_cap,
+                          size_t *out_len);
+
+// Computes HMA (Hull Moving Average)
+enum M4rsStatus m4rs_hma(const struct M4rsIndexEntry *entries,
+                         size_t len,
+                         size_t duration,
+                         struct M4rsIndexEntry *out,
+                         size_t out_cap,
+                         size_t *out_len);
+
+// Computes Momentum
+enum M4rsStatus m4rs_momentum(const struct M4rsIndexEntry *entries,
+                              size_t len,
+                              size_t duration,
+                              struct M4rsIndexEntry *out,
+                              size_t out_cap,
+                              size_t *out_len);
+
+// Computes RCI (Rank Correlation Index)
+enum M4rsStatus m4rs_rci(const struct M4rsIndexEntry *entries,
+                         size_t len,
+                         size_t duration,
+                         struct M4rsIndexEntry *out,
+                         size_t out_cap,
+                         size_t *out_len);
+
+// Computes RSI (Relative Strength Index)
+enum M4rsStatus m4rs_rsi(const struct M4rsIndexEntry *entries,
+                         size_t len,
+                         size_t duration,
+                         struct M4rsIndexEntry *out,
+                         size_t out_cap,
+                         size_t *out_len);
+
+// Computes Standard Deviation
+enum M4rsStatus m4rs_standard_deviation(const struct M4rsIndexEntry *entries,
+                                        size_t len,
+                                        size_t duration,
+                                        struct M4rsIndexEntry *out,
+                                        size_t out_cap,
+                                        size_t *out_len);
+
+// Computes Envelope
+enum M4rsStatus m4rs_envelope(const struct M4rsIndexEntry *entries,
+                              size_t len,
+                              float percent,
+                              struct M4rsEnvelopeEntry *out,
+                              size_t out_cap,
+                              size_t *out_len);
+
+// Computes Bolinger Band
+enum M4rsStatus m4rs_bolinger_band(const struct M4rsIndexEntry *entries,
+                                   size_t len,
+                                   size_t duration,
+                                   struct M4rsBollingerBandEntry *out,
+                                   size_t out_cap,
+                                   size_t *out_len);
+
+// Computes MACD
+enum M4rsStatus m4rs_macd(const struct M4rsIndexEntry *entries,
+                          size_t len,
+                          size_t short_duration,
+                          size_t long_duration,
+                          size_t signal_duration,
+                          struct M4rsMacdEntry *out,
+                          size_t out_cap,
+                          size_t *out_len);
+
+// Computes ATR (Average True Range)
+enum M4rsStatus m4rs_atr(const struct M4rsCandlestick *entries,
+                         size_t len,
+                         size_t duration,
+                         struct M4rsIndexEntry *out,
+                         size_t out_cap,
+                         size_t *out_len);
+
+// Computes CCI (Commodity Channel Index)
+enum M4rsStatus m4rs_cci(const struct M4rsCandlestick *entries,
+                         size_t len,
+                         size_t duration,
+                         struct M4rsIndexEntry *out,
+                         size_t out_cap,
+                         size_t *out_len);
+
+// Computes VWMA (Volume Weighted Moving Average)
+enum M4rsStatus m4rs_vwma(const struct M4rsCandlestick *entries,
+                          size_t len,
+                          size_t duration,
+                          struct M4rsIndexEntry *out,
+                          size_t out_cap,
+                          size_t *out_len);
+
+// Computes Williams %R
+enum M4rsStatus m4rs_williams_percent_r(const struct M4rsCandlestick *entries,
+                                        size_t len,
+                                        size_t duration,
+                                        struct M4rsIndexEntry *out,
+                                        size_t out_cap,
+                                        size_t *out_len);
+
+// Computes Awesome Oscillator
+enum M4rsStatus m4rs_awesome_oscillator(const struct M4rsCandlestick *entries,
+                                        size_t len,
+                                        size_t short_duration,
+                                        size_t long_duration,
+                                        struct M4rsIndexEntry *out,
+                                        size_t out_cap,
+                                        size_t *out_len);
+
+// Computes Parabolic SAR
+enum M4rsStatus m4rs_parabolic_sar(const struct M4rsCandlestick *entries,
+                                   size_t len,
+                                   float af_init,
+                                   float af_step,
+                                   float af_max,
+                                   struct M4rsIndexEntry *out,
+                                   size_t out_cap,
+                                   size_t *out_len);
+
+// Computes DMI/ADX
+enum M4rsStatus m4rs_dmi(const struct M4rsCandlestick *entries,
+                         size_t len,
+                         size_t duration,
+                         struct M4rsDmiEntry *out,
+                         size_t out_cap,
+                         size_t *out_len);
+
+// Computes Stochastics
+enum M4rsStatus m4rs_stochastics(const struct M4rsCandlestick *entries,
+                                 size_t len,
+                                 size_t duration_k,
+                                 size_t duration_d,
+                                 struct M4rsStochasticsEntry *out,
+                                 size_t out_cap,
+                                 size_t *out_len);
+
+// Computes Slow Stochastics
+enum M4rsStatus m4rs_slow_stochastics(const struct M4rsCandlestick *entries,
+                                      size_t len,
+                                      size_t duration_k,
+                                      size_t duration_d,
+                                      size_t duration_sd,
+                                      struct M4rsSlowStochasticsEntry *out,
+                                      size_t out_cap,
+                                      size_t *out_len);
+
+// Computes Williams Fractals
+enum M4rsStatus m4rs_williams_fractals(const struct M4rsCandlestick *entries,
+                                       size_t len,
+                                       size_t duration,
+                                       struct M4rsWilliamsFractalsEntry *out,
+                                       size_t out_cap,
+                                       size_t *out_len);
+
+// Computes Heikin Ashi
+enum M4rsStatus m4rs_heikin_ashi(const struct M4rsCandlestick *entries,
+                                 size_t len,
+                                 struct M4rsCandlestick *out,
+                                 size_t out_cap,
+                                 size_t *out_len);
+
+// Computes Ichimoku Kinko Hyo
+enum M4rsStatus m4rs_ichimoku(const struct M4rsCandlestick *entries,
+                              size_t len,
+                              size_t conversion_line_len,
+                              size_t base_line_len,
+                              size_t leading_span_b_len,
+                              size_t lagging_span,
+                              struct M4rsIchimokuPoint *out,
+                              size_t out_cap,
+                              size_t *out_len);
+
+// Creates new Stream, which must be released by [`m4rs_stream_free`]
+struct M4rsStream *m4rs_stream_new(enum M4rsStreamKind kind, size_t duration);
+
+// Pushes a candlestick and writes the latest indicator value to `out`
+enum M4rsStatus m4rs_stream_push(struct M4rsStream *stream,
+                                 const struct M4rsCandlestick *entry,
+                                 double *out);
+
+// Returns the number of candlesticks pushed to the stream
+size_t m4rs_stream_len(const struct M4rsStream *stream);
+
+// Releases Stream created by [`m4rs_stream_new`]
+void m4rs_stream_free(struct M4rsStream *stream);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* M4RS_H */
@@ -0,0 +1,76 @@
+//! A/D (Accumulation/Distribution Line)
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get A/D calculation result
+//! let result = m4rs::accumulation_distribution(&candlesticks);
+//! ```
+
+use crate::{Candlestick, Error, IndexEntry};
+
+/// Returns A/D (Accumulation/Distribution Line) for given Candlestick list
+pub fn accumulation_distribution(entries: &[Candlestick]) -> Result<Vec<IndexEntry>, Error> {
+    Candlestick::validate_list(entries)?;
+
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.at);
+
+    Ok(sorted
+        .iter()
+        .scan(0.0, |z, x| {
+            *z += money_flow_volume(x);
+            Some(IndexEntry {
+                at: x.at,
+                value: *z,
+            })
+        })
+        .collect())
+}
+
+/// Returns money flow volume, which is volume weighted by the close location within the range
+pub(crate) fn money_flow_volume(x: &Candlestick) -> f64 {
+    let range = x.high - x.low;
+    if range == 0.0 {
+        0.0
+    } else {
+        ((x.close - x.low) - (x.high - x.close)) / range * x.volume
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Candlestick;
+
+    #[test]
+    fn test_accumulation_distribution() {
+        let xs = vec![
+            Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1200.0),
+            Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 800.0),
+            Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1500.0),
+            Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 900.0),
+        ];
+        let got = super::accumulation_distribution(&xs).unwrap();
+        assert_eq!(5, got.len());
+        assert_eq!(1719400005, got[4].at);
+        assert_eq!(
+            vec![0.0, 600.0, -200.0, -800.0, -980.0],
+            got.iter().map(|x| x.value).collect::<Vec<f64>>()
+        );
+
+        // no range means no money flow
+        let xs = vec![Candlestick::new(
+            1719400001, 100.0, 100.0, 100.0, 100.0, 1000.0,
+        )];
+        assert_eq!(0.0, super::accumulation_distribution(&xs).unwrap()[0].value);
+    }
+}
@@ -0,0 +1,100 @@
+//! ALMA (Arnaud Legoux Moving Average)
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get 9ALMA with offset 0.85 and sigma 6 calculation result
+//! let result = m4rs::alma(&candlesticks, 9, 0.85, 6.0);
+//! ```
+
+use crate::{Error, IndexEntry, IndexEntryLike};
+
+/// Returns ALMA (Arnaud Legoux Moving Average) for given IndexEntry list
+///
+/// Weights follow a Gaussian curve centered at `offset` (0.0 for the oldest, 1.0 for the latest entry)
+/// of the window, whose width is `duration / sigma`.
+pub fn alma(
+    entries: &[impl IndexEntryLike],
+    duration: usize,
+    offset: f32,
+    sigma: f32,
+) -> Result<Vec<IndexEntry>, Error> {
+    if !offset.is_finite() || offset < 0.0 {
+        return Err(Error::MustBePositiveF32 {
+            value: offset,
+            field: "offset".to_string(),
+        });
+    }
+    if !sigma.is_finite() || sigma <= 0.0 {
+        return Err(Error::MustBePositiveF32 {
+            value: sigma,
+            field: "sigma".to_string(),
+        });
+    }
+    if duration == 0 || entries.len() < duration {
+        return Ok(vec![]);
+    }
+    IndexEntry::validate_list(entries)?;
+
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.get_at());
+
+    let m = offset as f64 * (duration as f64 - 1.0);
+    let s = duration as f64 / sigma as f64;
+    let weights: Vec<f64> = (0..duration)
+        .map(|i| (-(i as f64 - m).powi(2) / (2.0 * s * s)).exp())
+        .collect();
+    let total: f64 = weights.iter().sum();
+
+    Ok(sorted
+        .windows(duration)
+        .map(|xs| IndexEntry {
+            at: xs.last().unwrap().get_at(),
+            value: xs
+                .iter()
+                .zip(&weights)
+                .fold(0.0, |z, (x, w)| z + x.get_value() * w)
+                / total,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, IndexEntry};
+
+    #[test]
+    fn test_alma() {
+        let xs = vec![
+            IndexEntry::new(1719400001, 110.0),
+            IndexEntry::new(1719400002, 130.0),
+            IndexEntry::new(1719400003, 120.0),
+            IndexEntry::new(1719400004, 95.0),
+            IndexEntry::new(1719400005, 82.0),
+            IndexEntry::new(1719400006, 115.0),
+            IndexEntry::new(1719400007, 145.0),
+            IndexEntry::new(1719400008, 155.0),
+        ];
+        let got = super::alma(&xs, 3, 0.85, 6.0).unwrap();
+        assert_eq!(6, got.len());
+        assert_eq!(1719400003, got[0].at);
+        assert!((got[0].value - 123.06691545048743).abs() < 1e-9);
+        assert!((got[1].value - 102.81998487889834).abs() < 1e-9);
+        assert!((got[5].value - 151.8058376722794).abs() < 1e-9);
+
+        for (offset, sigma) in [(0.85, 0.0), (0.85, f32::NAN), (-0.1, 6.0), (f32::NAN, 6.0)] {
+            assert!(matches!(
+                super::alma(&xs, 3, offset, sigma),
+                Err(Error::MustBePositiveF32 { .. })
+            ));
+        }
+    }
+}
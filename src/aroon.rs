@@ -0,0 +1,122 @@
+//! Aroon
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get Aroon of 25 bars
+//! let result = m4rs::aroon(&candlesticks, 25);
+//! ```
+
+use std::fmt::Display;
+
+use crate::{Candlestick, Error, IndexEntryLike};
+
+#[derive(Clone, Debug)]
+pub struct AroonEntry {
+    pub at: u64,
+    pub up: f64,
+    pub down: f64,
+    /// Aroon Up minus Aroon Down
+    pub oscillator: f64,
+}
+
+impl Display for AroonEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Aroon(at={} up={} down={} osc={})",
+            self.at, self.up, self.down, self.oscillator
+        )
+    }
+}
+
+impl IndexEntryLike for AroonEntry {
+    fn get_at(&self) -> u64 {
+        self.at
+    }
+
+    fn get_value(&self) -> f64 {
+        self.oscillator
+    }
+}
+
+/// Returns Aroon for given Candlestick list
+///
+/// Aroon Up/Down is 100 when the highest high/lowest low of the last `duration + 1` bars
+/// is at the latest bar, decreasing to 0 as it gets `duration` bars old. The latest one
+/// counts when the extreme is reached more than once.
+pub fn aroon(entries: &[Candlestick], duration: usize) -> Result<Vec<AroonEntry>, Error> {
+    if duration == 0 || entries.len() <= duration {
+        return Ok(vec![]);
+    }
+    Candlestick::validate_list(entries)?;
+
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.at);
+
+    let d = duration as f64;
+    Ok(sorted
+        .windows(duration + 1)
+        .map(|xs| {
+            // bars since the extreme, from the latest one
+            let since = |f: &dyn Fn(&Candlestick, &Candlestick) -> bool| {
+                xs.iter()
+                    .rev()
+                    .enumerate()
+                    .reduce(|z, x| if f(x.1, z.1) { x } else { z })
+                    .unwrap()
+                    .0 as f64
+            };
+            let up = (d - since(&|x, z| x.high > z.high)) / d * 100.0;
+            let down = (d - since(&|x, z| x.low < z.low)) / d * 100.0;
+            AroonEntry {
+                at: xs.last().unwrap().at,
+                up,
+                down,
+                oscillator: up - down,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Candlestick;
+
+    #[test]
+    fn test_aroon() {
+        let xs = vec![
+            Candlestick::new(1719400001, 110.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 130.0, 140.0, 100.0, 130.0, 1000.0),
+            Candlestick::new(1719400003, 120.0, 135.0, 120.0, 120.0, 1000.0),
+            Candlestick::new(1719400004, 95.0, 130.0, 80.0, 95.0, 1000.0),
+            Candlestick::new(1719400005, 82.0, 100.0, 70.0, 82.0, 1000.0),
+            Candlestick::new(1719400006, 115.0, 120.0, 80.0, 115.0, 1000.0),
+            Candlestick::new(1719400007, 145.0, 150.0, 110.0, 145.0, 1000.0),
+            Candlestick::new(1719400008, 155.0, 160.0, 140.0, 155.0, 1000.0),
+            Candlestick::new(1719400009, 150.0, 158.0, 145.0, 150.0, 1000.0),
+            Candlestick::new(1719400010, 160.0, 165.0, 148.0, 160.0, 1000.0),
+            Candlestick::new(1719400011, 140.0, 162.0, 135.0, 140.0, 1000.0),
+            Candlestick::new(1719400012, 135.0, 145.0, 128.0, 135.0, 1000.0),
+        ];
+        let got = super::aroon(&xs, 3).unwrap();
+        assert_eq!(9, got.len());
+        assert_eq!(1719400004, got[0].at);
+        assert!((got[0].up - 33.333333333333336).abs() < 1e-9);
+        assert_eq!(100.0, got[0].down);
+        assert_eq!(
+            (0.0, 100.0, -100.0),
+            (got[1].up, got[1].down, got[1].oscillator)
+        );
+        assert!((got[7].up - 66.66666666666667).abs() < 1e-9);
+        assert!((got[7].oscillator - -33.33333333333333).abs() < 1e-9);
+    }
+}
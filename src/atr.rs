@@ -25,8 +25,13 @@ pub fn atr(entries: &[Candlestick], duration: usize) -> Result<Vec<IndexEntry>,
     Candlestick::validate_list(entries)?;
 
     let mut sorted = entries.to_owned();
-    sorted.sort_by(|a, b| a.at.cmp(&b.at));
+    sorted.sort_by_key(|x| x.at);
 
+    rma(&true_range(&sorted), duration)
+}
+
+/// Returns true range from the 2nd of given sorted Candlestick list
+pub(crate) fn true_range(sorted: &[Candlestick]) -> Vec<IndexEntry> {
     let mut tr: Vec<IndexEntry> = vec![];
     for (i, x) in sorted.iter().enumerate() {
         if i == 0 {
@@ -41,5 +46,5 @@ pub fn atr(entries: &[Candlestick], duration: usize) -> Result<Vec<IndexEntry>,
             value: r1.max(r2).max(r3),
         });
     }
-    rma(&tr, duration)
+    tr
 }
@@ -39,7 +39,7 @@ pub fn awesome_oscillator(
     Candlestick::validate_list(entries)?;
 
     let mut sorted = entries.to_owned();
-    sorted.sort_by(|a, b| a.at.cmp(&b.at));
+    sorted.sort_by_key(|x| x.at);
 
     let median_prices: Vec<IndexEntry> = sorted
         .iter()
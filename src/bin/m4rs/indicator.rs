@@ -0,0 +1,80 @@
+//! Indicator columns given by `--ind` specs
+
+use m4rs::{Candlestick, Error, IndicatorSpec, INDICATORS};
+
+/// Output column of an indicator
+pub struct Column {
+    pub name: String,
+    pub values: Vec<(u64, f64)>,
+}
+
+impl Column {
+    pub fn candles(name: &str, xs: &[Candlestick], value: impl Fn(&Candlestick) -> f64) -> Self {
+        Column {
+            name: name.to_string(),
+            values: xs.iter().map(|x| (x.at, value(x))).collect(),
+        }
+    }
+}
+
+/// Column name prefix such as `ema_20` or `macd_12_26_9`
+pub fn label(spec: &IndicatorSpec) -> String {
+    [spec.info.name.to_string()]
+        .into_iter()
+        .chain(spec.params.iter().map(|x| x.as_f64().to_string()))
+        .collect::<Vec<String>>()
+        .join("_")
+}
+
+/// Computes columns named by label, suffixed with output names for multiple outputs
+pub fn compute(spec: &IndicatorSpec, candles: &[Candlestick]) -> Result<Vec<Column>, Error> {
+    let label = label(spec);
+    let output = spec.compute(candles)?;
+    Ok(output
+        .names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| Column {
+            name: if output.names.len() == 1 {
+                label.clone()
+            } else {
+                format!("{}_{}", label, name)
+            },
+            values: output
+                .rows
+                .iter()
+                .filter_map(|row| row.values[i].map(|v| (row.at, v)))
+                .collect(),
+        })
+        .collect())
+}
+
+/// Returns names of supported indicators with their default parameters
+pub fn usage() -> String {
+    INDICATORS
+        .iter()
+        .map(|info| {
+            let params: Vec<String> = info
+                .params
+                .iter()
+                .map(|x| format!("{}={}", x.name, x.default))
+                .collect();
+            format!("    {}({})", info.name, params.join(", "))
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_label() {
+        let spec = IndicatorSpec::parse("EMA:20").unwrap();
+        assert_eq!("ema_20", label(&spec));
+
+        let spec = IndicatorSpec::parse("macd(5)").unwrap();
+        assert_eq!("macd_5_26_9", label(&spec));
+    }
+}
@@ -0,0 +1,283 @@
+//! OHLCV input in CSV or JSON
+
+use m4rs::Candlestick;
+
+use crate::json::{self, Json};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+impl Format {
+    pub fn parse(s: &str) -> Result<Format, String> {
+        match s.to_ascii_lowercase().as_str() {
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format '{}', expected csv or json", s)),
+        }
+    }
+
+    /// Guesses format from file name, or from content when it is unknown
+    pub fn detect(path: Option<&str>, content: &str) -> Format {
+        match path.and_then(|x| x.rsplit_once('.')).map(|(_, ext)| ext) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Format::Json,
+            Some(ext) if ext.eq_ignore_ascii_case("csv") => Format::Csv,
+            _ if content.trim_start().starts_with(['[', '{']) => Format::Json,
+            _ => Format::Csv,
+        }
+    }
+}
+
+const AT_NAMES: [&str; 4] = ["at", "time", "timestamp", "date"];
+const FIELDS: [&str; 5] = ["open", "high", "low", "close", "volume"];
+
+pub fn read(content: &str, format: Format) -> Result<Vec<Candlestick>, String> {
+    match format {
+        Format::Csv => read_csv(content),
+        Format::Json => read_json(content),
+    }
+}
+
+/// Parses seconds since the Unix epoch, or RFC 3339 date-time without fractional seconds
+fn parse_at(s: &str) -> Option<u64> {
+    let s = s.trim();
+    if s.bytes().all(|x| x.is_ascii_digit()) {
+        s.parse().ok()
+    } else {
+        parse_rfc3339(s)
+    }
+}
+
+/// Parses timestamp in JSON, a non-negative integral number or a string as in CSV
+fn parse_json_at(x: &Json) -> Option<u64> {
+    match x {
+        Json::Number(x) if x.fract() == 0.0 && (0.0..u64::MAX as f64).contains(x) => {
+            Some(*x as u64)
+        }
+        Json::String(x) => parse_at(x),
+        _ => None,
+    }
+}
+
+/// Parses date-time such as `2024-06-26T11:00:00Z` or `2024-06-26T20:00:00+09:00`
+fn parse_rfc3339(s: &str) -> Option<u64> {
+    let num = |s: &str| -> Option<i64> {
+        (!s.is_empty() && s.bytes().all(|x| x.is_ascii_digit()))
+            .then(|| s.parse().ok())
+            .flatten()
+    };
+    let (date, rest) = (s.get(..10)?, s.get(10..)?);
+    let time = rest.strip_prefix(['T', 't', ' '])?;
+    let (time, offset) = (time.get(..8)?, time.get(8..)?);
+
+    let (year, month, day) = match date.split('-').collect::<Vec<_>>()[..] {
+        [y, m, d] if y.len() == 4 && m.len() == 2 && d.len() == 2 => (num(y)?, num(m)?, num(d)?),
+        _ => return None,
+    };
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => return None,
+    };
+    if !(1..=days_in_month).contains(&day) {
+        return None;
+    }
+    let seconds = match time.split(':').collect::<Vec<_>>()[..] {
+        [h, m, s] if h.len() == 2 && m.len() == 2 && s.len() == 2 => {
+            let (h, m, s) = (num(h)?, num(m)?, num(s)?);
+            (h < 24 && m < 60 && s < 60).then_some(h * 3600 + m * 60 + s)?
+        }
+        _ => return None,
+    };
+    let offset = match offset {
+        "Z" | "z" => 0,
+        _ => {
+            let sign = match offset.get(..1)? {
+                "+" => 1,
+                "-" => -1,
+                _ => return None,
+            };
+            match offset[1..].split(':').collect::<Vec<_>>()[..] {
+                [h, m] if h.len() == 2 && m.len() == 2 => {
+                    let (h, m) = (num(h)?, num(m)?);
+                    (h < 24 && m < 60).then_some(sign * (h * 3600 + m * 60))?
+                }
+                _ => return None,
+            }
+        }
+    };
+
+    // days since 1970-01-01 in proleptic Gregorian calendar
+    let y = if month <= 2 { year - 1 } else { year };
+    let (era, yoe) = (y.div_euclid(400), y.rem_euclid(400));
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let days = era * 146097 + yoe * 365 + yoe / 4 - yoe / 100 + doy - 719468;
+    u64::try_from(days * 86400 + seconds - offset).ok()
+}
+
+/// Reads CSV with a header row containing at (or time/timestamp/date), open, high, low, close and volume.
+/// Volume is optional and defaults to 0. Timestamps are seconds since the Unix epoch or RFC 3339 date-times.
+fn read_csv(content: &str) -> Result<Vec<Candlestick>, String> {
+    let mut lines = content
+        .lines()
+        .enumerate()
+        .filter(|(_, x)| !x.trim().is_empty());
+    let header: Vec<String> = match lines.next() {
+        Some((_, x)) => x
+            .split(',')
+            .map(|x| x.trim().to_ascii_lowercase())
+            .collect(),
+        None => return Ok(vec![]),
+    };
+    let position = |names: &[&str]| header.iter().position(|x| names.contains(&x.as_str()));
+    let at_pos = position(&AT_NAMES).ok_or("CSV header has no at/time/timestamp column")?;
+    let pos: Vec<Option<usize>> = FIELDS.iter().map(|x| position(&[x])).collect();
+    if let Some(i) = pos.iter().take(4).position(|x| x.is_none()) {
+        return Err(format!("CSV header has no {} column", FIELDS[i]));
+    }
+
+    lines
+        .map(|(n, line)| {
+            let cols: Vec<&str> = line.split(',').collect();
+            let at = cols
+                .get(at_pos)
+                .and_then(|x| parse_at(x))
+                .ok_or_else(|| format!("line {}: invalid timestamp", n + 1))?;
+            let mut values = [0.0; 5];
+            for (i, p) in pos.iter().enumerate() {
+                if let Some(p) = p {
+                    values[i] = cols
+                        .get(*p)
+                        .and_then(|x| x.trim().parse().ok())
+                        .ok_or_else(|| format!("line {}: invalid {}", n + 1, FIELDS[i]))?;
+                }
+            }
+            Ok(Candlestick::new(
+                at, values[0], values[1], values[2], values[3], values[4],
+            ))
+        })
+        .collect()
+}
+
+/// Reads JSON array of objects with OHLCV keys, or of `[at, open, high, low, close, volume]` arrays
+fn read_json(content: &str) -> Result<Vec<Candlestick>, String> {
+    let xs = match json::parse(content)? {
+        Json::Array(xs) => xs,
+        _ => return Err("JSON input must be an array".to_string()),
+    };
+    xs.iter()
+        .enumerate()
+        .map(|(i, x)| {
+            let (at, values): (Option<&Json>, Vec<Option<&Json>>) = match x {
+                Json::Array(row) => (row.first(), (1..=5).map(|i| row.get(i)).collect()),
+                Json::Object(_) => (
+                    AT_NAMES.iter().find_map(|k| x.get(k)),
+                    FIELDS.iter().map(|k| x.get(k)).collect(),
+                ),
+                _ => return Err(format!("entry {}: must be an object or an array", i)),
+            };
+            let at = at
+                .and_then(parse_json_at)
+                .ok_or_else(|| format!("entry {}: invalid timestamp", i))?;
+            let mut ohlcv = [0.0; 5];
+            for (j, v) in values.iter().enumerate() {
+                ohlcv[j] = match v {
+                    Some(v) => v
+                        .as_f64()
+                        .ok_or_else(|| format!("entry {}: invalid {}", i, FIELDS[j]))?,
+                    None if j == 4 => 0.0,
+                    None => return Err(format!("entry {}: missing {}", i, FIELDS[j])),
+                };
+            }
+            Ok(Candlestick::new(
+                at, ohlcv[0], ohlcv[1], ohlcv[2], ohlcv[3], ohlcv[4],
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_csv() {
+        let got = read(
+            "Time,Open,High,Low,Close,Volume\n1719400001,100,130,90,110,1000\n\n1719400002,110,140,100,130,1000\n",
+            Format::Csv,
+        )
+        .unwrap();
+        assert_eq!(2, got.len());
+        assert_eq!(1719400002, got[1].at);
+        assert_eq!(140.0, got[1].high);
+        assert_eq!(1000.0, got[1].volume);
+
+        let got = read("at,open,high,low\n1,1,1,1\n", Format::Csv);
+        assert_eq!(
+            Some("CSV header has no close column".to_string()),
+            got.err()
+        );
+    }
+
+    #[test]
+    fn test_read_json() {
+        let got = read(
+            r#"[{"at": 1719400001, "open": 100, "high": 130, "low": 90, "close": 110},
+                [1719400002, 110, 140, 100, 130, 1000]]"#,
+            Format::Json,
+        )
+        .unwrap();
+        assert_eq!(2, got.len());
+        assert_eq!(0.0, got[0].volume);
+        assert_eq!(130.0, got[1].close);
+    }
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(Format::Json, Format::detect(Some("a.JSON"), ""));
+        assert_eq!(Format::Csv, Format::detect(Some("a.csv"), "[]"));
+        assert_eq!(Format::Json, Format::detect(None, "  [{}]"));
+        assert_eq!(Format::Csv, Format::detect(None, "at,open"));
+    }
+
+    #[test]
+    fn test_parse_at() {
+        assert_eq!(Some(1719400001), parse_at(" 1719400001 "));
+        assert_eq!(None, parse_at("1719400001.7"));
+        assert_eq!(None, parse_at("-1"));
+        assert_eq!(None, parse_at("18446744073709551616"));
+        assert_eq!(Some(1719399600), parse_at("2024-06-26T11:00:00Z"));
+        assert_eq!(Some(1719399600), parse_at("2024-06-26 20:00:00+09:00"));
+        assert_eq!(Some(1719399600), parse_at("2024-06-26t07:00:00-04:00"));
+        assert_eq!(Some(951782400), parse_at("2000-02-29T00:00:00Z"));
+        assert_eq!(None, parse_at("2023-02-29T00:00:00Z"));
+        assert_eq!(None, parse_at("2024-06-26T11:00:00.5Z"));
+        assert_eq!(None, parse_at("2024-06-26"));
+        assert_eq!(None, parse_at("1969-12-31T23:59:59Z"));
+    }
+
+    #[test]
+    fn test_read_invalid_timestamp() {
+        let got = read(
+            "date,open,high,low,close\n2024-06-26T11:00:00Z,1,1,1,1\n1719400001.7,1,1,1,1\n",
+            Format::Csv,
+        );
+        assert_eq!(Some("line 3: invalid timestamp".to_string()), got.err());
+
+        let got = read(r#"[["2024-06-26T11:00:00Z", 1, 1, 1, 1]]"#, Format::Json).unwrap();
+        assert_eq!(1719399600, got[0].at);
+        for content in [
+            "[[1719400001.7, 1, 1, 1, 1]]",
+            "[[-1, 1, 1, 1, 1]]",
+            "[[1e20, 1, 1, 1, 1]]",
+        ] {
+            let got = read(content, Format::Json);
+            assert_eq!(Some("entry 0: invalid timestamp".to_string()), got.err());
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! Command-line tool computing indicators from OHLCV files
+//!
+//! ```sh
+//! m4rs --input candles.csv --ind ema:20 --ind rsi:14 --ind macd:12,26,9 --ind ichimoku
+//! m4rs --input candles.csv --ind "bollinger(20, 2)" --ind rsi --chart
+//! ```
+
+mod indicator;
+mod input;
+mod json;
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, IsTerminal, Read, Write},
+    process::ExitCode,
+};
+
+use indicator::Column;
+use input::Format;
+use m4rs::{Chart, IndicatorSpec};
+
+const USAGE: &str = "Usage: m4rs [OPTIONS] --ind <SPEC>...
+
+Reads OHLCV candlesticks from CSV or JSON and writes a CSV table of
+candlesticks joined with indicator values by timestamp.
+
+Options:
+    -i, --input <FILE>     Input file (default: stdin)
+    -f, --format <FORMAT>  Input format: csv or json (default: guessed)
+        --ind <SPEC>       Indicator as name(param,...) or name:param,..., repeatable
+        --chart            Print a chart to the terminal instead of CSV
+        --size <COLSxROWS> Size of the chart (default: 100x30)
+    -h, --help             Print help
+
+CSV input needs a header row with at (or time, timestamp, date), open,
+high, low, close and optional volume columns. JSON input is an array of
+objects with the same keys, or of [at, open, high, low, close, volume].
+Timestamps are seconds since the Unix epoch or RFC 3339 date-times such
+as 2024-06-26T11:00:00Z.
+
+Omitted trailing parameters fall back to their defaults.
+
+Indicators:";
+
+#[derive(Debug, Default)]
+struct Args {
+    input: Option<String>,
+    format: Option<Format>,
+    specs: Vec<IndicatorSpec>,
+    chart: Option<(usize, usize)>,
+    help: bool,
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut ret = Args::default();
+    let mut args = args.peekable();
+    while let Some(arg) = args.next() {
+        let (key, inline) = match arg.split_once('=') {
+            Some((k, v)) if k.starts_with("--") => (k.to_string(), Some(v.to_string())),
+            _ => (arg, None),
+        };
+        let mut value = |name: &str| {
+            inline
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("{} requires a value", name))
+        };
+        match key.as_str() {
+            "-h" | "--help" => ret.help = true,
+            "-i" | "--input" => ret.input = Some(value(&key)?),
+            "-f" | "--format" => ret.format = Some(Format::parse(&value(&key)?)?),
+            "--ind" => ret
+                .specs
+                .push(IndicatorSpec::parse(&value(&key)?).map_err(|e| e.to_string())?),
+            "--chart" => {
+                ret.chart.get_or_insert(DEFAULT_SIZE);
+            }
+            "--size" => ret.chart = Some(parse_size(&value(&key)?)?),
+            _ => return Err(format!("unknown argument '{}'", key)),
+        }
+    }
+    Ok(ret)
+}
+
+const DEFAULT_SIZE: (usize, usize) = (100, 30);
+
+fn parse_size(s: &str) -> Result<(usize, usize), String> {
+    s.split_once(['x', 'X'])
+        .and_then(|(w, h)| Some((w.trim().parse().ok()?, h.trim().parse().ok()?)))
+        .filter(|(w, h)| *w > 0 && *h > 0)
+        .ok_or_else(|| format!("invalid size '{}', expected COLSxROWS", s))
+}
+
+fn read_input(path: Option<&str>) -> Result<String, String> {
+    match path {
+        None | Some("-") => {
+            let mut s = String::new();
+            io::stdin()
+                .read_to_string(&mut s)
+                .map_err(|e| format!("failed to read stdin: {}", e))?;
+            Ok(s)
+        }
+        Some(path) => {
+            fs::read_to_string(path).map_err(|e| format!("failed to read {}: {}", path, e))
+        }
+    }
+}
+
+/// Writes columns joined by timestamp, leaving cells without value empty
+fn write_table(w: &mut impl Write, columns: &[Column]) -> io::Result<()> {
+    let mut rows: BTreeMap<u64, Vec<Option<f64>>> = BTreeMap::new();
+    for (i, column) in columns.iter().enumerate() {
+        for (at, value) in &column.values {
+            rows.entry(*at).or_insert_with(|| vec![None; columns.len()])[i] = Some(*value);
+        }
+    }
+    let header: Vec<&str> = columns.iter().map(|x| x.name.as_str()).collect();
+    writeln!(w, "at,{}", header.join(","))?;
+    for (at, values) in rows {
+        let cells: Vec<String> = values
+            .iter()
+            .map(|x| x.map(|x| x.to_string()).unwrap_or_default())
+            .collect();
+        writeln!(w, "{},{}", at, cells.join(","))?;
+    }
+    Ok(())
+}
+
+fn run() -> Result<(), String> {
+    let args = parse_args(std::env::args().skip(1))?;
+    if args.help {
+        println!("{}\n{}", USAGE, indicator::usage());
+        return Ok(());
+    }
+
+    let content = read_input(args.input.as_deref())?;
+    let format = args
+        .format
+        .unwrap_or_else(|| Format::detect(args.input.as_deref(), &content));
+    let mut candles = input::read(&content, format)?;
+    candles.sort_by_key(|x| x.at);
+
+    if let Some((cols, rows)) = args.chart {
+        let chart = args
+            .specs
+            .iter()
+            .fold(Chart::new(&candles), |z, x| z.indicator(x.clone()));
+        let ansi = io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+        let text = chart
+            .to_terminal(cols, rows, ansi)
+            .map_err(|e| e.to_string())?;
+        print!("{}", text);
+        return Ok(());
+    }
+
+    let mut columns = vec![
+        Column::candles("open", &candles, |x| x.open),
+        Column::candles("high", &candles, |x| x.high),
+        Column::candles("low", &candles, |x| x.low),
+        Column::candles("close", &candles, |x| x.close),
+        Column::candles("volume", &candles, |x| x.volume),
+    ];
+    for spec in &args.specs {
+        let xs = indicator::compute(spec, &candles).map_err(|e| format!("{}: {}", spec, e))?;
+        columns.extend(xs);
+    }
+
+    let stdout = io::stdout();
+    write_table(&mut stdout.lock(), &columns).map_err(|e| e.to_string())
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use m4rs::Candlestick;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_args() {
+        let args = [
+            "-i",
+            "a.json",
+            "--ind",
+            "ema:20",
+            "--ind=rsi",
+            "--format=csv",
+        ];
+        let got = parse_args(args.iter().map(|x| x.to_string())).unwrap();
+        assert_eq!(Some("a.json".to_string()), got.input);
+        assert_eq!(Some(Format::Csv), got.format);
+        assert_eq!(2, got.specs.len());
+        assert_eq!("rsi(14)", got.specs[1].to_string());
+
+        assert_eq!(None, got.chart);
+
+        let got = parse_args(["--chart"].iter().map(|x| x.to_string())).unwrap();
+        assert_eq!(Some(DEFAULT_SIZE), got.chart);
+        let got = parse_args(["--size=80x24"].iter().map(|x| x.to_string())).unwrap();
+        assert_eq!(Some((80, 24)), got.chart);
+
+        assert!(parse_args(["--size", "80"].iter().map(|x| x.to_string())).is_err());
+        assert!(parse_args(["--ind"].iter().map(|x| x.to_string())).is_err());
+        assert!(parse_args(["--foo"].iter().map(|x| x.to_string())).is_err());
+    }
+
+    #[test]
+    fn test_write_table() {
+        let candles = vec![
+            Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+            Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+        ];
+        let mut columns = vec![Column::candles("close", &candles, |x| x.close)];
+        let spec = IndicatorSpec::parse("sma:2").unwrap();
+        columns.extend(indicator::compute(&spec, &candles).unwrap());
+
+        let mut buf = vec![];
+        write_table(&mut buf, &columns).unwrap();
+        assert_eq!(
+            "at,close,sma_2\n1719400001,110,\n1719400002,130,120\n1719400003,120,125\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+}
@@ -17,9 +17,10 @@
 
 use std::fmt::Display;
 
-use crate::{Error, IndexEntry, IndexEntryLike};
+use crate::{index_entry::zip_tails, Error, IndexEntry, IndexEntryLike, MaType};
 
 #[derive(Clone, Debug)]
+#[repr(C)]
 pub struct BollingerBandEntry {
     pub at: u64,
     pub avg: f64,
@@ -84,6 +85,17 @@ impl IndexEntryLike for BollingerBandEntry {
 pub fn bolinger_band(
     entries: &[impl IndexEntryLike],
     duration: usize,
+) -> Result<Vec<BollingerBandEntry>, Error> {
+    bolinger_band_with_ma(entries, duration, MaType::Sma)
+}
+
+/// Returns Bolinger Band around the moving average selected by `ma`
+///
+/// Sigma is the standard deviation over `duration` regardless of `ma`.
+pub fn bolinger_band_with_ma(
+    entries: &[impl IndexEntryLike],
+    duration: usize,
+    ma: MaType,
 ) -> Result<Vec<BollingerBandEntry>, Error> {
     if duration == 0 || entries.len() < duration {
         return Ok(vec![]);
@@ -93,20 +105,26 @@ pub fn bolinger_band(
     let mut sorted = entries.to_owned();
     sorted.sort_by_key(|x| x.get_at());
 
-    Ok((0..sorted.len() - duration + 1)
-        .map(|i| {
-            let xs = sorted.iter().skip(i).take(duration);
+    let avgs = ma.calc(&sorted, duration)?;
+    let sigmas: Vec<IndexEntry> = sorted
+        .windows(duration)
+        .map(|xs| {
             let d = duration as f64;
-            let avg = xs.clone().fold(0.0, |z, x| z + x.get_value()) / d;
-            let sigma = xs
-                .clone()
-                .fold(0.0, |z, x| z + (x.get_value() - avg).powi(2) / d)
-                .sqrt();
-            BollingerBandEntry {
+            let mean = xs.iter().fold(0.0, |z, x| z + x.get_value()) / d;
+            IndexEntry {
                 at: xs.last().unwrap().get_at(),
-                avg,
-                sigma,
+                value: xs
+                    .iter()
+                    .fold(0.0, |z, x| z + (x.get_value() - mean).powi(2) / d)
+                    .sqrt(),
             }
         })
+        .collect();
+    Ok(zip_tails(&avgs, &sigmas)
+        .map(|(avg, sigma)| BollingerBandEntry {
+            at: avg.at,
+            avg: avg.value,
+            sigma: sigma.value,
+        })
         .collect())
 }
@@ -0,0 +1,436 @@
+//! Trading calendar telling when a market is open
+//!
+//! Timestamps are seconds since the Unix epoch, and local time of a calendar is
+//! a fixed offset from UTC.
+//!
+//! # Examples
+//! ```rust
+//! use m4rs::TradingCalendar;
+//!
+//! // 24/7 market such as crypto
+//! let next = m4rs::Continuous.next_bar(1719403200, 3600);
+//! assert_eq!(Some(1719406800), next);
+//!
+//! // Tokyo Stock Exchange, 9:00-11:30 and 12:30-15:30 in UTC+9 on weekdays
+//! let tse = m4rs::SessionCalendar::new(9 * 3600, &[(9 * 3600, 41400), (45000, 55800)])
+//!     .unwrap()
+//!     .holidays(&[(2024, 7, 15)]);
+//!
+//! // Next hourly bar after 11:00 on Friday 2024-07-12 is 12:30,
+//! // and after 15:00 is 9:00 on Tuesday 2024-07-16 since Monday is a holiday
+//! assert_eq!(Some(1720755000), tse.next_bar(1720749600, 3600));
+//! assert_eq!(Some(1721088000), tse.next_bar(1720764000, 3600));
+//!
+//! // NYSE, 9:30-16:00 in UTC-4 on weekdays, closing at 13:00 on 2024-07-03
+//! let nyse = m4rs::SessionCalendar::new(-4 * 3600, &[(34200, 57600)])
+//!     .unwrap()
+//!     .holidays(&[(2024, 7, 4)])
+//!     .half_days(&[((2024, 7, 3), 13 * 3600)]);
+//!
+//! // Expected 30 minute bars from 9:30 on 2024-07-03 to 10:00 on 2024-07-05
+//! let bars = nyse.bars(1720013400, 1720188000, 1800);
+//! assert_eq!(9, bars.len());
+//!
+//! // FX, 17:00-17:00 of the next day in UTC-4 opening from Sunday to Thursday
+//! let fx = m4rs::SessionCalendar::new(-4 * 3600, &[(61200, 147600)])
+//!     .unwrap()
+//!     .days([true, true, true, true, false, false, true]);
+//!
+//! // 16:00 on Friday 2024-07-05 is in the session opening on Thursday,
+//! // and the next hourly bar is 17:00 on Sunday
+//! assert!(fx.is_open(1720209600));
+//! assert_eq!(Some(1720386000), fx.next_bar(1720209600, 3600));
+//! ```
+
+use crate::Error;
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Calendar generating timestamps of bars
+pub trait TradingCalendar {
+    /// Returns true if a bar starting at `at` is traded
+    fn is_open(&self, at: u64) -> bool;
+
+    /// Returns timestamp of the bar of `interval` seconds following the one at `at`
+    ///
+    /// Steps by `interval` until a traded bar, and returns None if none is found within a year
+    /// or the timestamp overflows.
+    fn next_bar(&self, at: u64, interval: u64) -> Option<u64> {
+        if interval == 0 {
+            return None;
+        }
+        let limit = at.saturating_add(366 * SECONDS_PER_DAY as u64);
+        (1..)
+            .map_while(|i| interval.checked_mul(i).and_then(|x| at.checked_add(x)))
+            .take_while(|x| *x <= limit)
+            .find(|x| self.is_open(*x))
+    }
+
+    /// Returns timestamps of bars of `interval` seconds from `from` to `to` inclusive
+    ///
+    /// `from` is the first bar if it is traded, otherwise bars start at the next one.
+    fn bars(&self, from: u64, to: u64, interval: u64) -> Vec<u64> {
+        let first = if self.is_open(from) {
+            Some(from)
+        } else {
+            self.next_bar(from, interval)
+        };
+        std::iter::successors(first, |x| self.next_bar(*x, interval))
+            .take_while(|x| *x <= to)
+            .collect()
+    }
+}
+
+/// Calendar open at any time, such as crypto markets
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Continuous;
+
+impl TradingCalendar for Continuous {
+    fn is_open(&self, _at: u64) -> bool {
+        true
+    }
+
+    fn next_bar(&self, at: u64, interval: u64) -> Option<u64> {
+        at.checked_add(interval).filter(|_| interval > 0)
+    }
+}
+
+/// Calendar of regular sessions on trading days of week, closed on holidays and closing early on half-days
+///
+/// Bars start at the open of each session and every interval within it.
+/// Fields are private so that sessions are always validated by [`SessionCalendar::new`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SessionCalendar {
+    /// Seconds of local time ahead of UTC
+    utc_offset: i64,
+    /// Open and close of sessions in seconds from local midnight of the day they open,
+    /// in ascending order
+    sessions: Vec<(u32, u32)>,
+    /// Trading days of week from Monday to Sunday, on which sessions open
+    days: [bool; 7],
+    /// Local dates closed as (year, month, day)
+    holidays: Vec<(i32, u32, u32)>,
+    /// Local dates as (year, month, day) closing early, with the close in seconds from local midnight
+    half_days: Vec<((i32, u32, u32), u32)>,
+}
+
+impl SessionCalendar {
+    /// Returns calendar of `sessions` on weekdays
+    ///
+    /// Sessions must open before the local midnight following it and close after the open,
+    /// in ascending order without overlapping. A session closing after the midnight,
+    /// such as 18:00-17:00 of futures, has the close beyond 86400 and belongs to the day it opens,
+    /// and it must close by the open of the first session of the next day.
+    pub fn new(utc_offset: i64, sessions: &[(u32, u32)]) -> Result<Self, Error> {
+        let day = SECONDS_PER_DAY as u32;
+        let mut end = 0;
+        for &(open, close) in sessions {
+            if open < end || open >= day || close <= open {
+                return Err(Error::InvalidSession { open, close });
+            }
+            end = close;
+        }
+        if let Some(&(open, close)) = sessions.first() {
+            if end > open + day {
+                return Err(Error::InvalidSession { open, close });
+            }
+        }
+        Ok(SessionCalendar {
+            utc_offset,
+            sessions: sessions.to_vec(),
+            days: [true, true, true, true, true, false, false],
+            holidays: vec![],
+            half_days: vec![],
+        })
+    }
+
+    /// Returns calendar open all day on weekdays
+    pub fn weekdays(utc_offset: i64) -> Self {
+        SessionCalendar {
+            utc_offset,
+            sessions: vec![(0, SECONDS_PER_DAY as u32)],
+            days: [true, true, true, true, true, false, false],
+            holidays: vec![],
+            half_days: vec![],
+        }
+    }
+
+    /// Returns seconds of local time ahead of UTC
+    pub fn get_utc_offset(&self) -> i64 {
+        self.utc_offset
+    }
+
+    /// Returns open and close of sessions in seconds from local midnight of the day they open
+    pub fn get_sessions(&self) -> &[(u32, u32)] {
+        &self.sessions
+    }
+
+    /// Returns trading days of week from Monday to Sunday
+    pub fn get_days(&self) -> [bool; 7] {
+        self.days
+    }
+
+    /// Returns local dates closed as (year, month, day)
+    pub fn get_holidays(&self) -> &[(i32, u32, u32)] {
+        &self.holidays
+    }
+
+    /// Returns local dates as (year, month, day) closing early, with the close in seconds from local midnight
+    pub fn get_half_days(&self) -> &[((i32, u32, u32), u32)] {
+        &self.half_days
+    }
+
+    /// Sets trading days of week from Monday to Sunday
+    pub fn days(mut self, days: [bool; 7]) -> Self {
+        self.days = days;
+        self
+    }
+
+    /// Adds local dates closed as (year, month, day)
+    pub fn holidays(mut self, dates: &[(i32, u32, u32)]) -> Self {
+        self.holidays.extend_from_slice(dates);
+        self
+    }
+
+    /// Sets early close in seconds from local midnight of given local dates as (year, month, day)
+    ///
+    /// Sessions opening at or after the early close are closed on the date.
+    /// Holidays and half-days apply to sessions opening on the date.
+    pub fn half_days(mut self, dates: &[((i32, u32, u32), u32)]) -> Self {
+        self.half_days.extend_from_slice(dates);
+        self
+    }
+
+    fn local_day(&self, at: u64) -> i64 {
+        signed(at)
+            .saturating_add(self.utc_offset)
+            .div_euclid(SECONDS_PER_DAY)
+    }
+
+    /// Returns open and close timestamps of sessions on the local day counted from 1970-01-01
+    fn sessions_on(&self, day: i64) -> Vec<(i64, i64)> {
+        let date = date_from_days(day);
+        // 1970-01-01 is Thursday
+        if !self.days[(day + 3).rem_euclid(7) as usize] || self.holidays.contains(&date) {
+            return vec![];
+        }
+        let early_close = self
+            .half_days
+            .iter()
+            .find(|(x, _)| *x == date)
+            .map_or(u32::MAX, |(_, close)| *close);
+        let midnight = day
+            .saturating_mul(SECONDS_PER_DAY)
+            .saturating_sub(self.utc_offset);
+        self.sessions
+            .iter()
+            .filter(|(open, _)| *open < early_close)
+            .map(|(open, close)| {
+                (
+                    midnight.saturating_add(*open as i64),
+                    midnight.saturating_add((*close).min(early_close) as i64),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns open and close timestamps of the session containing `at`
+    ///
+    /// Sessions opening on the previous day are looked up too since they may close after midnight.
+    fn session_at(&self, at: u64) -> Option<(i64, i64)> {
+        let day = self.local_day(at);
+        let at = signed(at);
+        (day.saturating_sub(1)..=day)
+            .flat_map(|x| self.sessions_on(x))
+            .find(|(open, close)| *open <= at && at < *close)
+    }
+}
+
+impl TradingCalendar for SessionCalendar {
+    fn is_open(&self, at: u64) -> bool {
+        self.session_at(at).is_some()
+    }
+
+    fn next_bar(&self, at: u64, interval: u64) -> Option<u64> {
+        if interval == 0 {
+            return None;
+        }
+        if self
+            .session_at(at)
+            .is_some_and(|(_, close)| interval < (close - signed(at)) as u64)
+        {
+            return Some(at + interval);
+        }
+        // open of the next session
+        let day = self.local_day(at);
+        (day..=day.saturating_add(366))
+            .flat_map(|x| self.sessions_on(x))
+            .map(|(open, _)| open)
+            .find(|x| *x > signed(at))
+            .map(|x| x as u64)
+    }
+}
+
+/// Returns timestamp as i64, saturating beyond its range
+fn signed(at: u64) -> i64 {
+    i64::try_from(at).unwrap_or(i64::MAX)
+}
+
+/// Returns (year, month, day) of days since 1970-01-01 in proleptic Gregorian calendar
+pub(crate) fn date_from_days(days: i64) -> (i32, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year as i32, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Continuous, SessionCalendar, TradingCalendar};
+
+    #[test]
+    fn test_date_from_days() {
+        assert_eq!((1970, 1, 1), super::date_from_days(0));
+        assert_eq!((1969, 12, 31), super::date_from_days(-1));
+        assert_eq!((2000, 2, 29), super::date_from_days(11016));
+        assert_eq!((2024, 6, 26), super::date_from_days(19900));
+    }
+
+    #[test]
+    fn test_continuous() {
+        assert!(Continuous.is_open(0));
+        assert_eq!(Some(86400), Continuous.next_bar(0, 86400));
+        assert_eq!(None, Continuous.next_bar(0, 0));
+        assert_eq!(None, Continuous.next_bar(u64::MAX, 1));
+    }
+
+    #[test]
+    fn test_next_bar_overflow() {
+        struct Closed;
+        impl TradingCalendar for Closed {
+            fn is_open(&self, _at: u64) -> bool {
+                false
+            }
+        }
+        assert_eq!(None, Closed.next_bar(u64::MAX - 1, 1));
+        assert_eq!(None, Closed.next_bar(1719400001, u64::MAX));
+
+        let calendar = SessionCalendar::weekdays(0);
+        // interval beyond the session skips to the next one on Monday
+        assert_eq!(Some(1719792000), calendar.next_bar(1719532800, u64::MAX));
+        assert!(!calendar.is_open(u64::MAX));
+        assert_eq!(None, calendar.next_bar(u64::MAX - 1, 3600));
+    }
+
+    #[test]
+    fn test_weekdays() {
+        // Friday 2024-06-28 and Saturday 2024-06-29 in UTC
+        let calendar = SessionCalendar::weekdays(0);
+        assert!(calendar.is_open(1719532800));
+        assert!(!calendar.is_open(1719619200));
+        // daily bar on Friday is followed by Monday
+        assert_eq!(Some(1719792000), calendar.next_bar(1719532800, 86400));
+        // Friday 2024-06-28 23:00 in UTC is Saturday in UTC+2
+        assert!(calendar.is_open(1719615600));
+        assert!(!SessionCalendar::weekdays(2 * 3600).is_open(1719615600));
+    }
+
+    #[test]
+    fn test_session_calendar() {
+        // 9:30-16:00 in UTC-4 on weekdays except 2024-07-04
+        let calendar = SessionCalendar::new(-4 * 3600, &[(34200, 57600)])
+            .unwrap()
+            .holidays(&[(2024, 7, 4)]);
+        // 9:30 and 15:30 on Wednesday 2024-07-03
+        assert!(calendar.is_open(1720013400));
+        assert_eq!(Some(1720035000), calendar.next_bar(1720031400, 3600));
+        // 15:30 on Wednesday is followed by 9:30 on Friday
+        assert_eq!(Some(1720186200), calendar.next_bar(1720035000, 3600));
+        assert!(!calendar.is_open(1720099800));
+
+        let calendar = calendar.days([false; 7]);
+        assert_eq!(None, calendar.next_bar(1720035000, 3600));
+    }
+
+    #[test]
+    fn test_lunch_break() {
+        // 9:00-11:30 and 12:30-15:30 in UTC+9, on Friday 2024-07-12
+        let calendar = SessionCalendar::new(9 * 3600, &[(32400, 41400), (45000, 55800)]).unwrap();
+        // 2 hourly bars from 11:00 does not skip the afternoon open at 12:30
+        assert_eq!(Some(1720755000), calendar.next_bar(1720749600, 7200));
+        assert!(!calendar.is_open(1720753200));
+    }
+
+    #[test]
+    fn test_half_days() {
+        // 9:30-16:00 in UTC-4, closing at 13:00 on Wednesday 2024-07-03
+        let calendar = SessionCalendar::new(-4 * 3600, &[(34200, 57600)])
+            .unwrap()
+            .half_days(&[((2024, 7, 3), 13 * 3600)]);
+        // 12:30 and 13:00 on 2024-07-03
+        assert!(calendar.is_open(1720024200));
+        assert!(!calendar.is_open(1720026000));
+        // 12:30 is followed by 9:30 on Thursday
+        assert_eq!(Some(1720099800), calendar.next_bar(1720024200, 1800));
+    }
+
+    #[test]
+    fn test_bars() {
+        // daily bars from Friday 2024-06-28 to Tuesday 2024-07-02 in UTC
+        let got = SessionCalendar::weekdays(0).bars(1719532800, 1719878400, 86400);
+        assert_eq!(vec![1719532800, 1719792000, 1719878400], got);
+        // starting on Saturday
+        let got = SessionCalendar::weekdays(0).bars(1719619200, 1719878400, 86400);
+        assert_eq!(vec![1719792000, 1719878400], got);
+        assert_eq!(5, Continuous.bars(1719532800, 1719878400, 86400).len());
+
+        // 9:30-16:00 in UTC-4 with hourly bars on Friday 2024-07-05
+        let calendar = SessionCalendar::new(-4 * 3600, &[(34200, 57600)]).unwrap();
+        let got = calendar.bars(1720180800, 1720224000, 3600);
+        assert_eq!(7, got.len());
+        assert_eq!(1720186200, got[0]);
+        assert_eq!(1720207800, got[6]);
+    }
+
+    #[test]
+    fn test_overnight_session() {
+        // 18:00-17:00 of the next day in UTC-5 opening from Sunday to Thursday
+        let calendar = SessionCalendar::new(-5 * 3600, &[(64800, 147600)])
+            .unwrap()
+            .days([true, true, true, true, false, false, true]);
+        assert_eq!(&[(64800, 147600)], calendar.get_sessions());
+        assert_eq!(-5 * 3600, calendar.get_utc_offset());
+        // 18:00 on Sunday 2024-07-07 and 3:00 on Monday
+        assert!(calendar.is_open(1720393200));
+        assert!(calendar.is_open(1720425600));
+        // 16:00 on Friday 2024-07-12 is followed by 18:00 on Sunday
+        assert!(calendar.is_open(1720818000));
+        assert!(!calendar.is_open(1720821600));
+        assert_eq!(Some(1720998000), calendar.next_bar(1720818000, 3600));
+        // 17:30 on Monday is in the break
+        assert!(!calendar.is_open(1720477800));
+    }
+
+    #[test]
+    fn test_invalid_sessions() {
+        let invalid = [
+            vec![(3600, 3600)],
+            vec![(7200, 3600)],
+            vec![(86400, 90000)],
+            vec![(32400, 55800), (45000, 57600)],
+            vec![(64800, 151300)],
+            vec![(32400, 41400), (64800, 120000)],
+        ];
+        for sessions in invalid {
+            assert!(SessionCalendar::new(0, &sessions).is_err());
+        }
+        assert!(SessionCalendar::new(0, &[(0, 86400)]).is_ok());
+        assert!(SessionCalendar::new(0, &[(32400, 41400), (64800, 118800)]).is_ok());
+    }
+}
@@ -2,10 +2,11 @@
 
 use std::fmt::Display;
 
-use crate::{Error, IndexEntry, IndexEntryLike};
+use crate::{Error, HighLowCloseLike, IndexEntry, IndexEntryLike};
 
 /// Candlestick entry
 #[derive(Debug, Clone)]
+#[repr(C)]
 pub struct Candlestick {
     pub at: u64,
     pub open: f64,
@@ -35,6 +36,28 @@ impl IndexEntryLike for Candlestick {
     }
 }
 
+impl HighLowCloseLike for Candlestick {
+    fn get_high(&self) -> f64 {
+        self.high
+    }
+
+    fn get_low(&self) -> f64 {
+        self.low
+    }
+
+    fn get_close(&self) -> f64 {
+        self.close
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        IndexEntry::validate_field(self.at, self.open, "open")?;
+        IndexEntry::validate_field(self.at, self.high, "high")?;
+        IndexEntry::validate_field(self.at, self.low, "low")?;
+        IndexEntry::validate_field(self.at, self.close, "close")?;
+        IndexEntry::validate_field(self.at, self.volume, "volume")
+    }
+}
+
 impl Candlestick {
     /// Creates new Candlestick instance
     pub fn new(at: u64, open: f64, high: f64, low: f64, close: f64, volume: f64) -> Candlestick {
@@ -50,11 +73,7 @@ impl Candlestick {
 
     pub(crate) fn validate_list(xs: &[Self]) -> Result<(), Error> {
         for x in xs {
-            IndexEntry::validate_field(x.at, x.open, "open")?;
-            IndexEntry::validate_field(x.at, x.high, "high")?;
-            IndexEntry::validate_field(x.at, x.low, "low")?;
-            IndexEntry::validate_field(x.at, x.close, "close")?;
-            IndexEntry::validate_field(x.at, x.volume, "volume")?;
+            x.validate()?;
         }
         Ok(())
     }
@@ -118,8 +137,6 @@ impl Candlestick {
 
 #[cfg(test)]
 mod tests {
-    use std::f64::{INFINITY, NAN};
-
     use crate::{Candlestick, Error, IndexEntryLike};
 
     #[test]
@@ -215,7 +232,7 @@ mod tests {
     #[test]
     fn test_validate_list() {
         // valid list
-        let res = Candlestick::validate_list(&vec![
+        let res = Candlestick::validate_list(&[
             Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
             Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
             Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
@@ -225,10 +242,10 @@ mod tests {
         assert!(res.is_ok());
 
         // invalid: contains NAN
-        let res = Candlestick::validate_list(&vec![
+        let res = Candlestick::validate_list(&[
             Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
             Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
-            Candlestick::new(1719400003, 130.0, NAN, 120.0, 120.0, 1000.0),
+            Candlestick::new(1719400003, 130.0, f64::NAN, 120.0, 120.0, 1000.0),
             Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
             Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
         ]);
@@ -237,11 +254,11 @@ mod tests {
         );
 
         // invalid: contains INFINITY
-        let res = Candlestick::validate_list(&vec![
+        let res = Candlestick::validate_list(&[
             Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
             Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
             Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
-            Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, INFINITY),
+            Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, f64::INFINITY),
             Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
         ]);
         assert!(
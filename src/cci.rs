@@ -25,7 +25,7 @@ pub fn cci(entries: &[Candlestick], duration: usize) -> Result<Vec<IndexEntry>,
     Candlestick::validate_list(entries)?;
 
     let mut sorted = entries.to_owned();
-    sorted.sort_by(|a, b| a.at.cmp(&b.at));
+    sorted.sort_by_key(|x| x.at);
 
     let tp: Vec<IndexEntry> = sorted.iter().map(|x| x.to_typical_price_entry()).collect();
     let ma = sma(&tp, duration)?;
@@ -51,7 +51,7 @@ fn average_deviations(xs: &[IndexEntry], duration: usize) -> Vec<IndexEntry> {
     (0..=(xs.len() - duration))
         .map(|i| xs.iter().skip(i).take(duration))
         .map(|xs| IndexEntry {
-            at: xs.clone().last().unwrap().at,
+            at: xs.clone().next_back().unwrap().at,
             value: average_deviation(&xs.map(|x| x.get_value()).collect::<Vec<f64>>()),
         })
         .collect()
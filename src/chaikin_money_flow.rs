@@ -0,0 +1,82 @@
+//! CMF (Chaikin Money Flow)
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get CMF calculation result
+//! let result = m4rs::chaikin_money_flow(&candlesticks, 20);
+//! ```
+
+use crate::{accumulation_distribution::money_flow_volume, Candlestick, Error, IndexEntry};
+
+/// Returns CMF (Chaikin Money Flow) for given Candlestick list
+///
+/// CMF is 0 for a window without volume, such as one of bars filled in gaps.
+pub fn chaikin_money_flow(
+    entries: &[Candlestick],
+    duration: usize,
+) -> Result<Vec<IndexEntry>, Error> {
+    if duration == 0 || entries.len() < duration {
+        return Ok(vec![]);
+    }
+    Candlestick::validate_list(entries)?;
+
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.at);
+
+    Ok(sorted
+        .windows(duration)
+        .map(|xs| {
+            let (mfv, v) = xs.iter().fold((0.0, 0.0), |z, x| {
+                (z.0 + money_flow_volume(x), z.1 + x.volume)
+            });
+            IndexEntry {
+                at: xs.last().unwrap().at,
+                value: if v == 0.0 { 0.0 } else { mfv / v },
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Candlestick;
+
+    #[test]
+    fn test_chaikin_money_flow() {
+        let xs = vec![
+            Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1200.0),
+            Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 800.0),
+            Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1500.0),
+            Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 900.0),
+        ];
+        let got = super::chaikin_money_flow(&xs, 3).unwrap();
+        assert_eq!(3, got.len());
+        assert_eq!(1719400003, got[0].at);
+        assert!((got[0].value - -0.0666666666666667).abs() < 1e-9);
+        assert!((got[1].value - -0.2285714285714286).abs() < 1e-9);
+        assert!((got[2].value - -0.49375).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_chaikin_money_flow_without_volume() {
+        let xs = vec![
+            Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 0.0),
+            Candlestick::new(1719400002, 110.0, 110.0, 110.0, 110.0, 0.0),
+            Candlestick::new(1719400003, 110.0, 140.0, 100.0, 130.0, 1000.0),
+        ];
+        let got = super::chaikin_money_flow(&xs, 2).unwrap();
+        assert_eq!(2, got.len());
+        assert_eq!(0.0, got[0].value);
+        assert!((got[1].value - 0.5).abs() < 1e-9);
+    }
+}
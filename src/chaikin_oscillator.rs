@@ -0,0 +1,73 @@
+//! Chaikin Oscillator
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get Chaikin Oscillator calculation result
+//! let result = m4rs::chaikin_oscillator(&candlesticks, 3, 10);
+//! ```
+
+use crate::{accumulation_distribution, ema, Candlestick, Error, IndexEntry};
+
+/// Returns Chaikin Oscillator, the difference between short and long EMAs of A/D line
+pub fn chaikin_oscillator(
+    entries: &[Candlestick],
+    short_duration: usize,
+    long_duration: usize,
+) -> Result<Vec<IndexEntry>, Error> {
+    if long_duration < short_duration {
+        return Err(Error::LongDurationIsNotGreaterThanShortDuration {
+            short_duration,
+            long_duration,
+        });
+    }
+    if short_duration == 0 || entries.len() < long_duration {
+        return Ok(vec![]);
+    }
+    let ad = accumulation_distribution(entries)?;
+    let ema_s = ema(&ad, short_duration)?;
+    let ema_l = ema(&ad, long_duration)?;
+    Ok(ema_l
+        .iter()
+        .filter_map(|l| {
+            ema_s.iter().find(|s| s.at == l.at).map(|s| IndexEntry {
+                at: l.at,
+                value: s.value - l.value,
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Candlestick, Error};
+
+    #[test]
+    fn test_chaikin_oscillator() {
+        let xs = vec![
+            Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1200.0),
+            Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 800.0),
+            Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1500.0),
+            Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 900.0),
+        ];
+        let got = super::chaikin_oscillator(&xs, 2, 3).unwrap();
+        assert_eq!(2, got.len());
+        assert_eq!(1719400004, got[0].at);
+        assert!((got[0].value - -211.1111111111111).abs() < 1e-9);
+        assert!((got[1].value - -178.1481481481481).abs() < 1e-9);
+
+        assert!(matches!(
+            super::chaikin_oscillator(&xs, 3, 2),
+            Err(Error::LongDurationIsNotGreaterThanShortDuration { .. })
+        ));
+    }
+}
@@ -0,0 +1,348 @@
+//! Chart of candlesticks with indicators
+//!
+//! Overlays and sub-panes are laid out from the chart metadata of the indicator
+//! registry, so any registered indicator can be drawn. Timestamps of candlesticks
+//! and indicator values are merged on the x axis, which keeps lines displaced into
+//! the future such as Ichimoku leading spans.
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Render candlesticks with SMA over them and RSI in a pane below
+//! let chart = m4rs::Chart::new(&candlesticks)
+//!     .indicator("sma(3)".parse().unwrap())
+//!     .indicator("rsi(3)".parse().unwrap());
+//! let svg = chart.to_svg(800, 600).unwrap();
+//! assert!(svg.starts_with("<svg"));
+//! ```
+
+use crate::{Candlestick, Error, IndexEntry, IndexEntryLike, IndicatorSpec, Pane, PlotStyle};
+
+/// Candlesticks with indicators to be rendered
+#[derive(Clone, Debug)]
+pub struct Chart {
+    candles: Vec<Candlestick>,
+    indicators: Vec<IndicatorSpec>,
+    series: Vec<(String, Vec<IndexEntry>)>,
+}
+
+impl Chart {
+    pub fn new(candles: &[Candlestick]) -> Chart {
+        let mut candles = candles.to_owned();
+        candles.sort_by_key(|x| x.at);
+        Chart {
+            candles,
+            indicators: vec![],
+            series: vec![],
+        }
+    }
+
+    /// Adds an indicator, drawn over candlesticks or in a pane of its own by its chart metadata
+    pub fn indicator(mut self, spec: IndicatorSpec) -> Chart {
+        self.indicators.push(spec);
+        self
+    }
+
+    /// Adds a line of any entries over candlesticks, such as a custom indicator
+    pub fn series(mut self, name: &str, entries: &[impl IndexEntryLike]) -> Chart {
+        let mut xs: Vec<IndexEntry> = entries
+            .iter()
+            .map(|x| IndexEntry::new(x.get_at(), x.get_value()))
+            .collect();
+        xs.sort_by_key(|x| x.at);
+        self.series.push((name.to_string(), xs));
+        self
+    }
+
+    /// Computes indicators and lays them out into panels sharing the x axis
+    pub(crate) fn layout(&self) -> Result<Layout, Error> {
+        Candlestick::validate_list(&self.candles)?;
+        for (_, xs) in &self.series {
+            IndexEntry::validate_list(xs)?;
+        }
+        let outputs = self
+            .indicators
+            .iter()
+            .map(|x| x.compute(&self.candles))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut ats: Vec<u64> = self
+            .candles
+            .iter()
+            .map(|x| x.at)
+            .chain(outputs.iter().flat_map(|x| x.rows.iter().map(|r| r.at)))
+            .chain(
+                self.series
+                    .iter()
+                    .flat_map(|(_, xs)| xs.iter().map(|x| x.at)),
+            )
+            .collect();
+        ats.sort();
+        ats.dedup();
+        let index = |at: u64| ats.binary_search(&at).unwrap();
+
+        let mut candles = vec![None; ats.len()];
+        for x in &self.candles {
+            candles[index(x.at)] = Some(x.clone());
+        }
+        let mut panels = vec![Panel::new(String::new(), None, &[], 3)];
+        panels[0].candles = candles.clone();
+
+        for (spec, output) in self.indicators.iter().zip(&outputs) {
+            let chart = &spec.info.chart;
+            let panel = match chart.pane {
+                Pane::Overlay => &mut panels[0],
+                Pane::Separate => {
+                    panels.push(Panel::new(spec.to_string(), chart.bounds, chart.levels, 1));
+                    panels.last_mut().unwrap()
+                }
+            };
+
+            let column = |name: &str| {
+                let mut values = vec![None; ats.len()];
+                if let Some(i) = output.names.iter().position(|x| *x == name) {
+                    for row in &output.rows {
+                        values[index(row.at)] = row.values[i];
+                    }
+                }
+                values
+            };
+
+            let mut positions = vec![];
+            let mut has_candles = false;
+            for info in spec.info.outputs {
+                let values = column(info.name);
+                let values = match info.style {
+                    PlotStyle::Candlestick => {
+                        has_candles = true;
+                        continue;
+                    }
+                    PlotStyle::MarkerAbove | PlotStyle::MarkerBelow => values
+                        .iter()
+                        .zip(&candles)
+                        .map(|(v, c)| match (v, c) {
+                            (Some(v), Some(c)) if *v != 0.0 => Some(match info.style {
+                                PlotStyle::MarkerAbove => c.high,
+                                _ => c.low,
+                            }),
+                            _ => None,
+                        })
+                        .collect(),
+                    _ => values,
+                };
+                let name = match (output.names.len(), chart.pane) {
+                    (1, _) => spec.to_string(),
+                    (_, Pane::Overlay) => format!("{} {}", spec.info.name, info.name),
+                    (_, Pane::Separate) => info.name.to_string(),
+                };
+                positions.push((info.name, panel.series.len()));
+                panel.series.push(Series {
+                    name,
+                    style: info.style,
+                    color: panel.colors,
+                    values,
+                });
+                panel.colors += 1;
+            }
+
+            if has_candles {
+                let [open, high, low, close] = ["open", "high", "low", "close"].map(column);
+                panel.candles = (0..ats.len())
+                    .map(|i| match (open[i], high[i], low[i], close[i]) {
+                        (Some(o), Some(h), Some(l), Some(c)) => {
+                            Some(Candlestick::new(ats[i], o, h, l, c, 0.0))
+                        }
+                        _ => None,
+                    })
+                    .collect();
+            }
+
+            let position = |name: &str| positions.iter().find(|(x, _)| *x == name).map(|x| x.1);
+            let fills: Vec<(usize, usize)> = chart
+                .fills
+                .iter()
+                .filter_map(|(a, b)| Some((position(a)?, position(b)?)))
+                .collect();
+            panel.fills.extend(fills);
+        }
+
+        for (name, xs) in &self.series {
+            let mut values = vec![None; ats.len()];
+            for x in xs {
+                values[index(x.at)] = Some(x.value);
+            }
+            let panel = &mut panels[0];
+            panel.series.push(Series {
+                name: name.clone(),
+                style: PlotStyle::Line,
+                color: panel.colors,
+                values,
+            });
+            panel.colors += 1;
+        }
+
+        for panel in &mut panels {
+            panel.fit_range();
+        }
+        Ok(Layout { ats, panels })
+    }
+}
+
+/// Indicators laid out into vertically stacked panels sharing the x axis
+pub(crate) struct Layout {
+    pub ats: Vec<u64>,
+    pub panels: Vec<Panel>,
+}
+
+impl Layout {
+    /// Returns heights of panels splitting given height by their weights
+    pub fn heights(&self, height: f64) -> Vec<f64> {
+        let total: u32 = self.panels.iter().map(|x| x.weight).sum();
+        self.panels
+            .iter()
+            .map(|x| height * x.weight as f64 / total as f64)
+            .collect()
+    }
+}
+
+/// Pane of a chart: price with overlays, or a separate indicator
+pub(crate) struct Panel {
+    pub title: String,
+    pub bounds: Option<(f64, f64)>,
+    pub levels: Vec<f64>,
+    /// Candlesticks by x index, empty when the panel has none
+    pub candles: Vec<Option<Candlestick>>,
+    pub series: Vec<Series>,
+    /// Pairs of series indices whose area between is filled
+    pub fills: Vec<(usize, usize)>,
+    /// Relative height
+    pub weight: u32,
+    pub min: f64,
+    pub max: f64,
+    colors: usize,
+}
+
+impl Panel {
+    fn new(title: String, bounds: Option<(f64, f64)>, levels: &[f64], weight: u32) -> Panel {
+        Panel {
+            title,
+            bounds,
+            levels: levels.to_vec(),
+            candles: vec![],
+            series: vec![],
+            fills: vec![],
+            weight,
+            min: 0.0,
+            max: 1.0,
+            colors: 0,
+        }
+    }
+
+    /// Sets value range covering bounds, levels and all values, padded when unbounded
+    fn fit_range(&mut self) {
+        let values = self
+            .candles
+            .iter()
+            .flatten()
+            .flat_map(|x| [x.high, x.low])
+            .chain(
+                self.series
+                    .iter()
+                    .flat_map(|x| x.values.iter().flatten().copied()),
+            )
+            .chain(
+                self.series
+                    .iter()
+                    .filter(|x| x.style == PlotStyle::Histogram)
+                    .map(|_| 0.0),
+            )
+            .chain(self.levels.iter().copied())
+            .chain(self.bounds.iter().flat_map(|(a, b)| [*a, *b]));
+        let (min, max) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), x| {
+            (min.min(x), max.max(x))
+        });
+        let (min, max) = if min > max {
+            (0.0, 1.0)
+        } else if min == max {
+            (min - 1.0, max + 1.0)
+        } else {
+            (min, max)
+        };
+        let pad = if self.bounds.is_some() {
+            0.0
+        } else {
+            (max - min) * 0.05
+        };
+        self.min = min - pad;
+        self.max = max + pad;
+    }
+}
+
+/// Values of an indicator output by x index
+pub(crate) struct Series {
+    pub name: String,
+    pub style: PlotStyle,
+    /// Index of the series within its panel, used to pick a color
+    pub color: usize,
+    pub values: Vec<Option<f64>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout() {
+        let xs = vec![
+            Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+            Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+            Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+            Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+        ];
+        let chart = Chart::new(&xs)
+            .indicator("ichimoku(2, 2, 3, 2)".parse().unwrap())
+            .indicator("rsi(2)".parse().unwrap())
+            .indicator("fractals(1)".parse().unwrap())
+            .indicator("heikin_ashi".parse().unwrap())
+            .series("close", &xs);
+        let got = chart.layout().unwrap();
+
+        // lagging span and leading spans are displaced beyond candlesticks
+        assert_eq!(1719400000, got.ats[0]);
+        assert_eq!(1719400006, got.ats[6]);
+        assert_eq!(3, got.panels.len());
+
+        let main = &got.panels[0];
+        assert_eq!(8, main.series.len());
+        assert_eq!("close", main.series[7].name);
+        assert_eq!(Some(82.0), main.series[7].values[5]);
+        assert_eq!(vec![(2, 3)], main.fills);
+        assert!(main.candles[0].is_none() && main.candles[6].is_none());
+        let fractal_up = &main.series[5];
+        assert_eq!(PlotStyle::MarkerAbove, fractal_up.style);
+        assert_eq!(
+            vec![None, None, Some(140.0), None, None, None, None],
+            fractal_up.values
+        );
+
+        let rsi = &got.panels[1];
+        assert_eq!("rsi(2)", rsi.title);
+        assert_eq!((0.0, 100.0), (rsi.min, rsi.max));
+        assert_eq!(vec![30.0, 70.0], rsi.levels);
+
+        let heikin_ashi = &got.panels[2];
+        assert!(heikin_ashi.series.is_empty());
+        assert_eq!(5, heikin_ashi.candles.iter().flatten().count());
+
+        assert_eq!(vec![60.0, 20.0, 20.0], got.heights(100.0));
+    }
+}
@@ -0,0 +1,97 @@
+//! Choppiness Index
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get Choppiness Index of 14 bars
+//! let result = m4rs::choppiness_index(&candlesticks, 14);
+//! ```
+
+use crate::{atr::true_range, Candlestick, Error, IndexEntry};
+
+/// Returns Choppiness Index for given Candlestick list
+///
+/// Sum of true range over `duration` relative to the range between highest high and
+/// lowest low, scaled from 0 (trending) to 100 (choppy). `duration` must be 2 or more.
+/// Windows without range between high and low, such as flat bars, have no entry.
+pub fn choppiness_index(
+    entries: &[Candlestick],
+    duration: usize,
+) -> Result<Vec<IndexEntry>, Error> {
+    if duration < 2 || entries.len() <= duration {
+        return Ok(vec![]);
+    }
+    Candlestick::validate_list(entries)?;
+
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.at);
+
+    let trs = true_range(&sorted);
+    Ok(sorted[1..]
+        .windows(duration)
+        .zip(trs.windows(duration))
+        .filter_map(|(xs, trs)| {
+            let highest = xs.iter().map(|x| x.high).reduce(f64::max).unwrap();
+            let lowest = xs.iter().map(|x| x.low).reduce(f64::min).unwrap();
+            if highest == lowest {
+                return None;
+            }
+            let tr = trs.iter().fold(0.0, |z, x| z + x.value);
+            Some(IndexEntry {
+                at: xs.last().unwrap().at,
+                value: (tr / (highest - lowest)).log10() / (duration as f64).log10() * 100.0,
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Candlestick;
+
+    #[test]
+    fn test_choppiness_index() {
+        let xs = vec![
+            Candlestick::new(1719400001, 110.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 130.0, 140.0, 100.0, 130.0, 1000.0),
+            Candlestick::new(1719400003, 120.0, 135.0, 120.0, 120.0, 1000.0),
+            Candlestick::new(1719400004, 95.0, 130.0, 80.0, 95.0, 1000.0),
+            Candlestick::new(1719400005, 82.0, 100.0, 70.0, 82.0, 1000.0),
+            Candlestick::new(1719400006, 115.0, 120.0, 80.0, 115.0, 1000.0),
+            Candlestick::new(1719400007, 145.0, 150.0, 110.0, 145.0, 1000.0),
+            Candlestick::new(1719400008, 155.0, 160.0, 140.0, 155.0, 1000.0),
+            Candlestick::new(1719400009, 150.0, 158.0, 145.0, 150.0, 1000.0),
+            Candlestick::new(1719400010, 160.0, 165.0, 148.0, 160.0, 1000.0),
+            Candlestick::new(1719400011, 140.0, 162.0, 135.0, 140.0, 1000.0),
+            Candlestick::new(1719400012, 135.0, 145.0, 128.0, 135.0, 1000.0),
+        ];
+        let got = super::choppiness_index(&xs, 3).unwrap();
+        assert_eq!(9, got.len());
+        assert_eq!(1719400004, got[0].at);
+        assert!((got[0].value - 50.93842420185074).abs() < 1e-9);
+        assert!((got[4].value - 20.31140135750123).abs() < 1e-9);
+        assert!((got[8].value - 45.50795186673206).abs() < 1e-9);
+    }
+    #[test]
+    fn test_choppiness_index_flat() {
+        // flat bars filled in gaps
+        let xs = vec![
+            Candlestick::new(1719400001, 110.0, 110.0, 110.0, 110.0, 0.0),
+            Candlestick::new(1719400002, 110.0, 110.0, 110.0, 110.0, 0.0),
+            Candlestick::new(1719400003, 110.0, 110.0, 110.0, 110.0, 0.0),
+            Candlestick::new(1719400004, 110.0, 130.0, 90.0, 120.0, 1000.0),
+        ];
+        let got = super::choppiness_index(&xs, 2).unwrap();
+        assert_eq!(1, got.len());
+        assert_eq!(1719400004, got[0].at);
+        assert_eq!(0.0, got[0].value);
+    }
+}
@@ -0,0 +1,82 @@
+//! CMO (Chande Momentum Oscillator)
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get 9 bars CMO calculation result
+//! let result = m4rs::cmo(&candlesticks, 9);
+//! ```
+
+use crate::{Error, IndexEntry, IndexEntryLike};
+
+/// Returns CMO (Chande Momentum Oscillator) for given IndexEntry list
+///
+/// Difference between sums of gains and losses over `duration` divided by their total,
+/// ranging from -100 to 100. It is 0 when the value has not changed.
+pub fn cmo(entries: &[impl IndexEntryLike], duration: usize) -> Result<Vec<IndexEntry>, Error> {
+    if duration == 0 || entries.len() <= duration {
+        return Ok(vec![]);
+    }
+    IndexEntry::validate_list(entries)?;
+
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.get_at());
+
+    Ok(sorted
+        .windows(duration + 1)
+        .map(|xs| {
+            let (up, down) = xs.windows(2).fold((0.0, 0.0), |(up, down), x| {
+                let d = x[1].get_value() - x[0].get_value();
+                (up + d.max(0.0), down + (-d).max(0.0))
+            });
+            IndexEntry {
+                at: xs.last().unwrap().get_at(),
+                value: if up + down == 0.0 {
+                    0.0
+                } else {
+                    (up - down) / (up + down) * 100.0
+                },
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IndexEntry;
+
+    #[test]
+    fn test_cmo() {
+        let xs = vec![
+            IndexEntry::new(1719400001, 110.0),
+            IndexEntry::new(1719400002, 130.0),
+            IndexEntry::new(1719400003, 120.0),
+            IndexEntry::new(1719400004, 95.0),
+            IndexEntry::new(1719400005, 82.0),
+            IndexEntry::new(1719400006, 115.0),
+            IndexEntry::new(1719400007, 145.0),
+            IndexEntry::new(1719400008, 155.0),
+            IndexEntry::new(1719400009, 150.0),
+            IndexEntry::new(1719400010, 160.0),
+            IndexEntry::new(1719400011, 140.0),
+            IndexEntry::new(1719400012, 135.0),
+            IndexEntry::new(1719400013, 150.0),
+            IndexEntry::new(1719400014, 165.0),
+        ];
+        let got = super::cmo(&xs, 3).unwrap();
+        assert_eq!(11, got.len());
+        assert_eq!(1719400004, got[0].at);
+        assert!((got[0].value - -27.27272727272727).abs() < 1e-9);
+        assert_eq!(-100.0, got[1].value);
+        assert_eq!(100.0, got[4].value);
+        assert!((got[10].value - 71.42857142857143).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,154 @@
+//! Connors RSI
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get Connors RSI of 3RSI, 2 bars streak RSI and 100 bars percent rank
+//! let result = m4rs::connors_rsi(&candlesticks, 3, 2, 100);
+//! ```
+
+use std::fmt::Display;
+
+use crate::{index_entry::zip_tails, roc, rsi, Error, IndexEntry, IndexEntryLike};
+
+#[derive(Clone, Debug)]
+pub struct ConnorsRsiEntry {
+    pub at: u64,
+    /// Average of the three components
+    pub value: f64,
+    pub rsi: f64,
+    /// RSI of up/down streak length
+    pub streak_rsi: f64,
+    /// Percent rank of 1 bar ROC
+    pub percent_rank: f64,
+}
+
+impl Display for ConnorsRsiEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ConnorsRsi(at={} value={} rsi={} streak_rsi={} percent_rank={})",
+            self.at, self.value, self.rsi, self.streak_rsi, self.percent_rank
+        )
+    }
+}
+
+impl IndexEntryLike for ConnorsRsiEntry {
+    fn get_at(&self) -> u64 {
+        self.at
+    }
+
+    fn get_value(&self) -> f64 {
+        self.value
+    }
+}
+
+/// Returns Connors RSI for given IndexEntry list
+///
+/// Average of RSI of `duration_rsi`, RSI of streak over `duration_streak` and
+/// percent rank of 1 bar ROC among the previous `duration_rank` ones. The streak counts
+/// consecutive rises as positive and falls as negative, and the rank counts ROCs less than
+/// or equal to the current one, as TradingView's `ta.percentrank` does.
+pub fn connors_rsi(
+    entries: &[impl IndexEntryLike],
+    duration_rsi: usize,
+    duration_streak: usize,
+    duration_rank: usize,
+) -> Result<Vec<ConnorsRsiEntry>, Error> {
+    if duration_rank == 0 || entries.len() <= duration_rank + 1 {
+        return Ok(vec![]);
+    }
+    IndexEntry::validate_list(entries)?;
+
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.get_at());
+
+    let streaks: Vec<IndexEntry> = sorted
+        .windows(2)
+        .scan(0.0_f64, |z, xs| {
+            let (prev, cur) = (xs[0].get_value(), xs[1].get_value());
+            *z = if cur > prev {
+                z.max(0.0) + 1.0
+            } else if cur < prev {
+                z.min(0.0) - 1.0
+            } else {
+                0.0
+            };
+            Some(IndexEntry {
+                at: xs[1].get_at(),
+                value: *z,
+            })
+        })
+        .collect();
+    let ranks: Vec<IndexEntry> = roc(&sorted, 1)?
+        .windows(duration_rank + 1)
+        .map(|xs| {
+            let (cur, prevs) = xs.split_last().unwrap();
+            IndexEntry {
+                at: cur.at,
+                value: prevs.iter().filter(|x| x.value <= cur.value).count() as f64
+                    / duration_rank as f64
+                    * 100.0,
+            }
+        })
+        .collect();
+    let rsis = rsi(&sorted, duration_rsi)?;
+    let streak_rsis = rsi(&streaks, duration_streak)?;
+    let pairs: Vec<_> = zip_tails(&rsis, &streak_rsis).collect();
+    let n = pairs.len().min(ranks.len());
+    Ok(pairs[pairs.len() - n..]
+        .iter()
+        .zip(&ranks[ranks.len() - n..])
+        .filter(|((rsi, _), rank)| rsi.at == rank.at)
+        .map(|((rsi, streak_rsi), rank)| ConnorsRsiEntry {
+            at: rank.at,
+            value: (rsi.value + streak_rsi.value + rank.value) / 3.0,
+            rsi: rsi.value,
+            streak_rsi: streak_rsi.value,
+            percent_rank: rank.value,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IndexEntry;
+
+    #[test]
+    fn test_connors_rsi() {
+        let xs = vec![
+            IndexEntry::new(1719400001, 110.0),
+            IndexEntry::new(1719400002, 130.0),
+            IndexEntry::new(1719400003, 120.0),
+            IndexEntry::new(1719400004, 95.0),
+            IndexEntry::new(1719400005, 82.0),
+            IndexEntry::new(1719400006, 115.0),
+            IndexEntry::new(1719400007, 145.0),
+            IndexEntry::new(1719400008, 155.0),
+            IndexEntry::new(1719400009, 150.0),
+            IndexEntry::new(1719400010, 160.0),
+            IndexEntry::new(1719400011, 140.0),
+            IndexEntry::new(1719400012, 135.0),
+            IndexEntry::new(1719400013, 150.0),
+            IndexEntry::new(1719400014, 165.0),
+        ];
+        let got = super::connors_rsi(&xs, 2, 2, 5).unwrap();
+        assert_eq!(8, got.len());
+        assert_eq!(1719400007, got[0].at);
+        assert!((got[0].value - 83.32441085393377).abs() < 1e-9);
+        assert!((got[0].rsi - 87.21461187214612).abs() < 1e-9);
+        assert!((got[0].streak_rsi - 82.75862068965517).abs() < 1e-9);
+        assert_eq!(80.0, got[0].percent_rank);
+        assert!((got[4].value - 20.00838245019745).abs() < 1e-9);
+        assert_eq!(0.0, got[4].percent_rank);
+        assert!((got[7].value - 82.17808377360126).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,78 @@
+//! Coppock Curve
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get Coppock Curve of 10WMA of 14 and 11 bars ROC
+//! let result = m4rs::coppock(&candlesticks, 10, 14, 11);
+//! ```
+
+use crate::{index_entry::zip_tails, roc, wma, Error, IndexEntry, IndexEntryLike};
+
+/// Returns Coppock Curve, WMA of the sum of long and short ROCs, for given IndexEntry list
+pub fn coppock(
+    entries: &[impl IndexEntryLike],
+    wma_duration: usize,
+    long_roc_duration: usize,
+    short_roc_duration: usize,
+) -> Result<Vec<IndexEntry>, Error> {
+    if long_roc_duration < short_roc_duration {
+        return Err(Error::LongDurationIsNotGreaterThanShortDuration {
+            short_duration: short_roc_duration,
+            long_duration: long_roc_duration,
+        });
+    }
+    let roc_l = roc(entries, long_roc_duration)?;
+    let roc_s = roc(entries, short_roc_duration)?;
+    let sums: Vec<IndexEntry> = zip_tails(&roc_l, &roc_s)
+        .map(|(l, s)| IndexEntry {
+            at: l.at,
+            value: l.value + s.value,
+        })
+        .collect();
+    wma(&sums, wma_duration)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, IndexEntry};
+
+    #[test]
+    fn test_coppock() {
+        let xs = vec![
+            IndexEntry::new(1719400001, 110.0),
+            IndexEntry::new(1719400002, 130.0),
+            IndexEntry::new(1719400003, 120.0),
+            IndexEntry::new(1719400004, 95.0),
+            IndexEntry::new(1719400005, 82.0),
+            IndexEntry::new(1719400006, 115.0),
+            IndexEntry::new(1719400007, 145.0),
+            IndexEntry::new(1719400008, 155.0),
+            IndexEntry::new(1719400009, 150.0),
+            IndexEntry::new(1719400010, 160.0),
+            IndexEntry::new(1719400011, 140.0),
+            IndexEntry::new(1719400012, 135.0),
+            IndexEntry::new(1719400013, 150.0),
+            IndexEntry::new(1719400014, 165.0),
+        ];
+        let got = super::coppock(&xs, 2, 3, 2).unwrap();
+        assert_eq!(10, got.len());
+        assert_eq!(1719400005, got[0].at);
+        assert!((got[0].value - -59.246309246309245).abs() < 1e-9);
+        assert!((got[3].value - 125.6916150397202).abs() < 1e-9);
+        assert!((got[9].value - 27.017195767195762).abs() < 1e-9);
+
+        assert!(matches!(
+            super::coppock(&xs, 2, 2, 3),
+            Err(Error::LongDurationIsNotGreaterThanShortDuration { .. })
+        ));
+    }
+}
@@ -0,0 +1,212 @@
+//! Price adjustment for splits and dividends
+//!
+//! # Examples
+//! ```rust
+//! // Prepare daily candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719360000, 100.0, 110.0, 90.0, 100.0, 1000.0),
+//!     m4rs::Candlestick::new(1719446400, 100.0, 120.0, 100.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719532800, 55.0, 60.0, 50.0, 55.0, 2000.0),
+//! ];
+//!
+//! // 2-for-1 split on the 3rd day
+//! let actions = vec![m4rs::CorporateAction::Split {
+//!     ex_date: 1719532800,
+//!     ratio: 2.0,
+//! }];
+//!
+//! // Get prices adjusted to the latest ones, before calculating indicators
+//! let adjusted =
+//!     m4rs::adjust_corporate_actions(&candlesticks, &actions, m4rs::Adjustment::Backward)
+//!         .unwrap();
+//! assert_eq!(55.0, adjusted[1].close);
+//! let result = m4rs::sma(&adjusted, 3);
+//! ```
+
+use crate::{Candlestick, Error};
+
+/// Event changing price of a share without change in value
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CorporateAction {
+    /// Split into `ratio` shares per share, e.g. 4.0 for 4-for-1 and 0.1 for 1-for-10 reverse split
+    Split { ex_date: u64, ratio: f64 },
+    /// Cash dividend of `amount` per share
+    Dividend { ex_date: u64, amount: f64 },
+}
+
+/// Prices kept as they are in adjustment
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Adjustment {
+    /// Keeps the latest prices and adjusts ones before each action
+    Backward,
+    /// Keeps the earliest prices and adjusts ones on and after each action
+    Forward,
+}
+
+/// Returns given Candlestick list sorted and adjusted for corporate actions
+///
+/// Candlesticks before `ex_date` are those before an action. A split scales prices by
+/// the inverse of the ratio and volume by the ratio, and a dividend scales prices by
+/// 1 - amount / close of the last Candlestick before `ex_date`, ignored if there is no such one.
+pub fn adjust_corporate_actions(
+    entries: &[Candlestick],
+    actions: &[CorporateAction],
+    adjustment: Adjustment,
+) -> Result<Vec<Candlestick>, Error> {
+    Candlestick::validate_list(entries)?;
+
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.at);
+
+    // ex-date with factors of price and volume applied to Candlesticks before it
+    let factors = actions
+        .iter()
+        .map(|x| match *x {
+            CorporateAction::Split { ex_date, ratio } => {
+                if ratio.is_nan() || ratio <= 0.0 {
+                    return Err(Error::MustBePositive {
+                        at: ex_date,
+                        field: "split ratio".to_string(),
+                    });
+                }
+                Ok(Some((ex_date, 1.0 / ratio, ratio)))
+            }
+            CorporateAction::Dividend { ex_date, amount } => {
+                if amount.is_nan() || amount < 0.0 {
+                    return Err(Error::MustBePositive {
+                        at: ex_date,
+                        field: "dividend amount".to_string(),
+                    });
+                }
+                let Some(prev) = sorted.iter().rev().find(|x| x.at < ex_date) else {
+                    return Ok(None);
+                };
+                if prev.close == 0.0 {
+                    return Err(Error::DividedByZero {
+                        at: prev.at,
+                        field: "close".to_string(),
+                    });
+                }
+                let factor = 1.0 - amount / prev.close;
+                if factor <= 0.0 {
+                    return Err(Error::MustBePositive {
+                        at: ex_date,
+                        field: "dividend adjustment factor".to_string(),
+                    });
+                }
+                Ok(Some((ex_date, factor, 1.0)))
+            }
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(sorted
+        .iter()
+        .map(|x| {
+            let (price, volume) = factors.iter().flatten().fold(
+                (1.0, 1.0),
+                |(p, v), (ex_date, price, volume)| match (adjustment, x.at < *ex_date) {
+                    (Adjustment::Backward, true) => (p * price, v * volume),
+                    (Adjustment::Forward, false) => (p / price, v / volume),
+                    _ => (p, v),
+                },
+            );
+            Candlestick::new(
+                x.at,
+                x.open * price,
+                x.high * price,
+                x.low * price,
+                x.close * price,
+                x.volume * volume,
+            )
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Adjustment, CorporateAction};
+    use crate::{Candlestick, Error};
+
+    fn candlesticks() -> Vec<Candlestick> {
+        vec![
+            Candlestick::new(1719360000, 100.0, 110.0, 90.0, 100.0, 1000.0),
+            Candlestick::new(1719446400, 100.0, 120.0, 100.0, 110.0, 1000.0),
+            Candlestick::new(1719532800, 55.0, 60.0, 50.0, 55.0, 2000.0),
+            Candlestick::new(1719792000, 55.0, 62.0, 54.0, 60.0, 2000.0),
+            Candlestick::new(1719878400, 59.0, 61.0, 57.0, 58.0, 2000.0),
+        ]
+    }
+
+    fn actions() -> Vec<CorporateAction> {
+        vec![
+            CorporateAction::Dividend {
+                ex_date: 1719878400,
+                amount: 1.2,
+            },
+            CorporateAction::Split {
+                ex_date: 1719532800,
+                ratio: 2.0,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_backward() {
+        let got =
+            super::adjust_corporate_actions(&candlesticks(), &actions(), Adjustment::Backward)
+                .unwrap();
+        // dividend factor is 1 - 1.2 / 60
+        assert!((got[0].high - 110.0 * 0.49).abs() < 1e-9);
+        assert!((got[1].close - 110.0 * 0.49).abs() < 1e-9);
+        assert_eq!(2000.0, got[1].volume);
+        assert!((got[3].close - 60.0 * 0.98).abs() < 1e-9);
+        assert_eq!(2000.0, got[3].volume);
+        assert_eq!(
+            (59.0, 61.0, 57.0, 58.0),
+            (got[4].open, got[4].high, got[4].low, got[4].close)
+        );
+    }
+
+    #[test]
+    fn test_forward() {
+        let got = super::adjust_corporate_actions(&candlesticks(), &actions(), Adjustment::Forward)
+            .unwrap();
+        assert_eq!(110.0, got[1].close);
+        assert_eq!(1000.0, got[1].volume);
+        assert_eq!((110.0, 1000.0), (got[2].close, got[2].volume));
+        assert!((got[4].close - 58.0 * 2.0 / 0.98).abs() < 1e-9);
+        assert_eq!(1000.0, got[4].volume);
+    }
+
+    #[test]
+    fn test_invalid_actions() {
+        let xs = candlesticks();
+        let split = |ratio| CorporateAction::Split {
+            ex_date: 1719532800,
+            ratio,
+        };
+        assert!(matches!(
+            super::adjust_corporate_actions(&xs, &[split(0.0)], Adjustment::Backward),
+            Err(Error::MustBePositive { .. })
+        ));
+
+        // dividend larger than close
+        let dividend = CorporateAction::Dividend {
+            ex_date: 1719532800,
+            amount: 110.0,
+        };
+        assert!(matches!(
+            super::adjust_corporate_actions(&xs, &[dividend], Adjustment::Backward),
+            Err(Error::MustBePositive { .. })
+        ));
+
+        // dividend before the first candlestick is ignored
+        let dividend = CorporateAction::Dividend {
+            ex_date: 1719360000,
+            amount: 1.0,
+        };
+        let got = super::adjust_corporate_actions(&xs, &[dividend], Adjustment::Forward).unwrap();
+        assert_eq!(100.0, got[0].close);
+        assert_eq!(58.0, got[4].close);
+    }
+}
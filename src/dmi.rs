@@ -13,13 +13,17 @@
 //!
 //! // Get DMI calculation result
 //! let result = m4rs::dmi(&candlesticks, 14);
+//!
+//! // Get DMI with Wilder's smoothing in percentage, matching TradingView's `ta.dmi`
+//! let result = m4rs::dmi_wilder(&candlesticks, 14);
 //! ```
 
 use std::fmt::Display;
 
-use crate::{ema, Candlestick, Error, IndexEntry, IndexEntryLike};
+use crate::{ema, Candlestick, Error, IndexEntry, IndexEntryLike, MaType};
 
 #[derive(Clone, Debug)]
+#[repr(C)]
 pub struct DmiEntry {
     pub at: u64,
     pub plus_di: f64,
@@ -82,33 +86,120 @@ impl Calc {
 
 /// Returns DMI/ADX for given IndexEntry list
 pub fn dmi(entries: &[Candlestick], duration: usize) -> Result<Vec<DmiEntry>, Error> {
+    calc(entries, duration, |xs| wilder_ma(xs, duration))
+}
+
+/// Returns DMI/ADX smoothing DM, TR and DX by the moving average selected by `ma`
+///
+/// `dmi` approximates Wilder's smoothing by EMA of (duration * 2 - 1).
+/// See `dmi_wilder` for the exact one.
+pub fn dmi_with_ma(
+    entries: &[Candlestick],
+    duration: usize,
+    ma: MaType,
+) -> Result<Vec<DmiEntry>, Error> {
+    calc(entries, duration, |xs| ma.calc(xs, duration))
+}
+
+/// Returns DMI/ADX with Wilder's smoothing for given Candlestick list
+///
+/// +DI, -DI, DX and ADX are in percentage (0 to 100). DM, TR and DX are smoothed
+/// by RMA seeded with SMA of the first `duration` values, as TradingView's `ta.dmi`,
+/// so the first entry is at index `duration * 2 - 1`. TA-Lib seeds the smoothing
+/// slightly differently, so its first values differ and converge after a while.
+pub fn dmi_wilder(entries: &[Candlestick], duration: usize) -> Result<Vec<DmiEntry>, Error> {
+    if duration == 0 || entries.len() < duration * 2 {
+        return Ok(vec![]);
+    }
+    Candlestick::validate_list(entries)?;
+
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.at);
+
+    // DM is counted only when it exceeds the other, so both are zero on a tie
+    let calcs: Vec<Calc> = calc_dm(&sorted)
+        .into_iter()
+        .map(|x| {
+            if x.plus_dm == x.minus_dm {
+                Calc {
+                    plus_dm: 0.0,
+                    minus_dm: 0.0,
+                    ..x
+                }
+            } else {
+                x
+            }
+        })
+        .collect();
+    let plus_dm_ma = wilder_smooth(
+        &calcs.iter().map(|x| x.plus_dm).collect::<Vec<f64>>(),
+        duration,
+    );
+    let minus_dm_ma = wilder_smooth(
+        &calcs.iter().map(|x| x.minus_dm).collect::<Vec<f64>>(),
+        duration,
+    );
+    let tr_ma = wilder_smooth(&calcs.iter().map(|x| x.tr).collect::<Vec<f64>>(), duration);
+    let dis: Vec<(f64, f64, f64)> = tr_ma
+        .iter()
+        .zip(plus_dm_ma.iter().zip(&minus_dm_ma))
+        .map(|(tr, (plus_dm, minus_dm))| {
+            let (plus_di, minus_di) = if *tr == 0.0 {
+                (0.0, 0.0)
+            } else {
+                (plus_dm / tr * 100.0, minus_dm / tr * 100.0)
+            };
+            let sum = plus_di + minus_di;
+            let dx = (plus_di - minus_di).abs() / if sum == 0.0 { 1.0 } else { sum } * 100.0;
+            (plus_di, minus_di, dx)
+        })
+        .collect();
+    let adxs = wilder_smooth(&dis.iter().map(|x| x.2).collect::<Vec<f64>>(), duration);
+
+    // DI starts at the `duration`th DM, and ADX at the `duration`th DI
+    let skip = duration * 2 - 2;
+    Ok(calcs
+        .iter()
+        .skip(skip)
+        .zip(dis.iter().skip(duration - 1))
+        .zip(adxs)
+        .map(|((c, (plus_di, minus_di, dx)), adx)| DmiEntry {
+            at: c.at,
+            plus_di: *plus_di,
+            minus_di: *minus_di,
+            dx: *dx,
+            adx,
+        })
+        .collect())
+}
+
+fn calc(
+    entries: &[Candlestick],
+    duration: usize,
+    smooth: impl Fn(&[IndexEntry]) -> Result<Vec<IndexEntry>, Error>,
+) -> Result<Vec<DmiEntry>, Error> {
     if duration == 0 || entries.len() < duration {
         return Ok(vec![]);
     }
     Candlestick::validate_list(entries)?;
 
     let mut sorted = entries.to_owned();
-    sorted.sort_by(|a, b| a.at.cmp(&b.at));
+    sorted.sort_by_key(|x| x.at);
 
     let calcs = calc_dm(&sorted);
-    let plus_dm_ma = wilder_ma(
+    let plus_dm_ma = smooth(
         &calcs
             .iter()
             .map(|x| x.plus_dm())
             .collect::<Vec<IndexEntry>>(),
-        duration,
     )?;
-    let minus_dm_ma = wilder_ma(
+    let minus_dm_ma = smooth(
         &calcs
             .iter()
             .map(|x| x.minus_dm())
             .collect::<Vec<IndexEntry>>(),
-        duration,
-    )?;
-    let tr_ma = wilder_ma(
-        &calcs.iter().map(|x| x.tr()).collect::<Vec<IndexEntry>>(),
-        duration,
     )?;
+    let tr_ma = smooth(&calcs.iter().map(|x| x.tr()).collect::<Vec<IndexEntry>>())?;
     let dmis = tr_ma.iter().filter_map(|tr| {
         match (
             plus_dm_ma.iter().find(|x| x.at == tr.at),
@@ -128,7 +219,7 @@ pub fn dmi(entries: &[Candlestick], duration: usize) -> Result<Vec<DmiEntry>, Er
             }
         }
     });
-    let adxs = wilder_ma(
+    let adxs = smooth(
         &dmis
             .clone()
             .map(|x| IndexEntry {
@@ -136,7 +227,6 @@ pub fn dmi(entries: &[Candlestick], duration: usize) -> Result<Vec<DmiEntry>, Er
                 value: x.dx,
             })
             .collect::<Vec<IndexEntry>>(),
-        duration,
     )?;
     Ok(dmis
         .filter_map(|dmi| {
@@ -183,6 +273,59 @@ fn calc_dm(entries: &[Candlestick]) -> Vec<Calc> {
         .collect()
 }
 
+/// Wilder's smoothing seeded with SMA, including the seed at index `duration - 1`
+fn wilder_smooth(values: &[f64], duration: usize) -> Vec<f64> {
+    if values.len() < duration {
+        return vec![];
+    }
+    let n = duration as f64;
+    let seed = values.iter().take(duration).sum::<f64>() / n;
+    std::iter::once(seed)
+        .chain(values.iter().skip(duration).scan(seed, |z, x| {
+            *z = (*z * (n - 1.0) + x) / n;
+            Some(*z)
+        }))
+        .collect()
+}
+
 fn wilder_ma(entries: &[impl IndexEntryLike], duration: usize) -> Result<Vec<IndexEntry>, Error> {
     ema(entries, duration * 2 - 1)
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::Candlestick;
+
+    #[test]
+    fn test_dmi_wilder() {
+        // reference values follow the Pine Script definition of TradingView's `ta.dmi(3, 3)`
+        let xs = vec![
+            Candlestick::new(1719400001, 110.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 130.0, 140.0, 100.0, 130.0, 1000.0),
+            Candlestick::new(1719400003, 120.0, 135.0, 120.0, 120.0, 1000.0),
+            Candlestick::new(1719400004, 95.0, 130.0, 80.0, 95.0, 1000.0),
+            Candlestick::new(1719400005, 82.0, 100.0, 70.0, 82.0, 1000.0),
+            Candlestick::new(1719400006, 115.0, 120.0, 80.0, 115.0, 1000.0),
+            Candlestick::new(1719400007, 145.0, 150.0, 110.0, 145.0, 1000.0),
+            Candlestick::new(1719400008, 155.0, 160.0, 140.0, 155.0, 1000.0),
+            Candlestick::new(1719400009, 150.0, 158.0, 145.0, 150.0, 1000.0),
+            Candlestick::new(1719400010, 160.0, 165.0, 148.0, 160.0, 1000.0),
+            Candlestick::new(1719400011, 140.0, 162.0, 135.0, 140.0, 1000.0),
+            Candlestick::new(1719400012, 135.0, 145.0, 128.0, 135.0, 1000.0),
+        ];
+        let got = super::dmi_wilder(&xs, 3).unwrap();
+        assert_eq!(7, got.len());
+        assert_eq!(1719400006, got[0].at);
+        assert!((got[0].plus_di - 22.91666666666666).abs() < 1e-9);
+        assert!((got[0].minus_di - 22.916666666666668).abs() < 1e-9);
+        assert!(got[0].dx.abs() < 1e-9);
+        assert!((got[0].adx - 43.076923076923094).abs() < 1e-9);
+        assert!((got[1].dx - 47.928994082840234).abs() < 1e-9);
+        assert!((got[2].plus_di - 43.438320209973746).abs() < 1e-9);
+        assert!((got[4].adx - 57.32228944719196).abs() < 1e-9);
+        assert!((got[6].plus_di - 17.207357428136557).abs() < 1e-9);
+        assert!((got[6].minus_di - 27.408366342322573).abs() < 1e-9);
+        assert!((got[6].dx - 22.8641565172597).abs() < 1e-9);
+        assert!((got[6].adx - 33.47214266423962).abs() < 1e-9);
+    }
+}
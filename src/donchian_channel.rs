@@ -0,0 +1,107 @@
+//! Donchian Channel
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get Donchian Channel of 20 bars
+//! let result = m4rs::donchian_channel(&candlesticks, 20);
+//! ```
+
+use std::fmt::Display;
+
+use crate::{Candlestick, Error, IndexEntryLike};
+
+#[derive(Clone, Debug)]
+pub struct DonchianChannelEntry {
+    pub at: u64,
+    pub upper: f64,
+    pub lower: f64,
+    pub middle: f64,
+}
+
+impl Display for DonchianChannelEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "DonchianChannel(at={} upper={} lower={} middle={})",
+            self.at, self.upper, self.lower, self.middle,
+        )
+    }
+}
+
+impl IndexEntryLike for DonchianChannelEntry {
+    fn get_at(&self) -> u64 {
+        self.at
+    }
+
+    fn get_value(&self) -> f64 {
+        self.middle
+    }
+}
+
+/// Returns Donchian Channel, highest high and lowest low over `duration` bars, for given Candlestick list
+pub fn donchian_channel(
+    entries: &[Candlestick],
+    duration: usize,
+) -> Result<Vec<DonchianChannelEntry>, Error> {
+    if duration == 0 || entries.len() < duration {
+        return Ok(vec![]);
+    }
+    Candlestick::validate_list(entries)?;
+
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.at);
+
+    Ok(sorted
+        .windows(duration)
+        .map(|xs| {
+            let upper = xs.iter().map(|x| x.high).reduce(f64::max).unwrap();
+            let lower = xs.iter().map(|x| x.low).reduce(f64::min).unwrap();
+            DonchianChannelEntry {
+                at: xs.last().unwrap().at,
+                upper,
+                lower,
+                middle: (upper + lower) / 2.0,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Candlestick;
+
+    #[test]
+    fn test_donchian_channel() {
+        let xs = vec![
+            Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+            Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+            Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+            Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+        ];
+        let got = super::donchian_channel(&xs, 3).unwrap();
+        assert_eq!(3, got.len());
+        assert_eq!(1719400003, got[0].at);
+        assert_eq!(
+            (140.0, 90.0, 115.0),
+            (got[0].upper, got[0].lower, got[0].middle)
+        );
+        assert_eq!(
+            (140.0, 80.0, 110.0),
+            (got[1].upper, got[1].lower, got[1].middle)
+        );
+        assert_eq!(
+            (135.0, 70.0, 102.5),
+            (got[2].upper, got[2].lower, got[2].middle)
+        );
+    }
+}
@@ -23,11 +23,12 @@ use std::fmt::Display;
 use crate::{Error, IndexEntry, IndexEntryLike};
 
 #[derive(Clone, Debug)]
+#[repr(C)]
 pub struct EnvelopeEntry {
-    at: u64,
-    basis: f64,
-    upper: f64,
-    lower: f64,
+    pub at: u64,
+    pub basis: f64,
+    pub upper: f64,
+    pub lower: f64,
 }
 
 impl Display for EnvelopeEntry {
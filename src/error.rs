@@ -1,6 +1,7 @@
 use std::fmt::Display;
 
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub enum Error {
     ContainsNaN {
         at: u64,
@@ -22,6 +23,28 @@ pub enum Error {
         at: u64,
         field: String,
     },
+    MustBePositive {
+        at: u64,
+        field: String,
+    },
+    UnknownIndicator {
+        name: String,
+    },
+    InvalidSpec {
+        spec: String,
+    },
+    InvalidParameter {
+        name: String,
+        param: String,
+    },
+    TooManyParameters {
+        name: String,
+        max: usize,
+    },
+    InvalidSession {
+        open: u32,
+        close: u32,
+    },
 }
 
 impl Display for Error {
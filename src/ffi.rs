@@ -0,0 +1,1114 @@
+//! C ABI (requires `ffi` feature)
+//!
+//! Indicators are computed into caller-provided buffers.
+//! Every function returns a [`Status`] and writes the number of output
+//! entries to `out_len`. When `out_cap` is too small nothing is written to
+//! `out`, [`Status::BufferTooSmall`] is returned and `out_len` holds the
+//! required capacity.
+//!
+//! A [`Stream`] handle takes candlesticks pushed one by one and returns the
+//! latest value of a single value indicator on each push, keeping only the
+//! state needed for the next one.
+//!
+//! The C header is generated with [cbindgen](https://github.com/mozilla/cbindgen):
+//! ```sh
+//! cbindgen --config cbindgen.toml --output include/m4rs.h
+//! ```
+//!
+//! # Examples
+//! ```c
+//! #include "m4rs.h"
+//!
+//! M4rsIndexEntry entries[] = {{1719400001, 110.0}, {1719400002, 130.0}, {1719400003, 120.0}};
+//! M4rsIndexEntry out[3];
+//! size_t out_len = 0;
+//! if (m4rs_sma(entries, 3, 2, out, 3, &out_len) == M4RS_STATUS_OK) {
+//!     // out[0] = {1719400002, 120.0}, out[1] = {1719400003, 125.0}
+//! }
+//!
+//! M4rsStream *stream = m4rs_stream_new(M4RS_STREAM_KIND_ATR, 14);
+//! double atr = 0.0;
+//! M4rsStatus status = m4rs_stream_push(stream, &candle, &atr);
+//! m4rs_stream_free(stream);
+//! ```
+//!
+//! # Safety
+//! Input pointers must point to `len` valid entries (or may be null when
+//! `len` is 0), `out` must be valid for `out_cap` writes, and `out_len` must
+//! be a valid pointer. Stream handles must come from [`m4rs_stream_new`] and
+//! must not be used after [`m4rs_stream_free`].
+
+use std::{collections::VecDeque, slice};
+
+use crate::{
+    BollingerBandEntry, Candlestick, DmiEntry, EnvelopeEntry, Error, IchimokuData, IndexEntry,
+    IndexEntryLike, MacdEntry, SlowStochasticsEntry, StochasticsEntry, WilliamsFractalsEntry,
+};
+
+/// Result code of FFI functions
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Ok = 0,
+    NullPointer = 1,
+    BufferTooSmall = 2,
+    /// Stream has not got enough entries to produce a value yet
+    NotReady = 3,
+    /// Pushed entry is older than the last one in the stream
+    OutOfOrder = 4,
+    ContainsNan = 10,
+    ContainsInfinite = 11,
+    LongDurationIsNotGreaterThanShortDuration = 12,
+    MustBePositiveF32 = 13,
+    DividedByZero = 14,
+    UnknownIndicator = 15,
+    InvalidSpec = 16,
+    InvalidParameter = 17,
+    TooManyParameters = 18,
+    MustBePositive = 19,
+    InvalidSession = 20,
+}
+
+impl From<&Error> for Status {
+    fn from(e: &Error) -> Self {
+        match e {
+            Error::ContainsNaN { .. } => Status::ContainsNan,
+            Error::ContainsInfinite { .. } => Status::ContainsInfinite,
+            Error::LongDurationIsNotGreaterThanShortDuration { .. } => {
+                Status::LongDurationIsNotGreaterThanShortDuration
+            }
+            Error::MustBePositiveF32 { .. } => Status::MustBePositiveF32,
+            Error::DividedByZero { .. } => Status::DividedByZero,
+            Error::UnknownIndicator { .. } => Status::UnknownIndicator,
+            Error::InvalidSpec { .. } => Status::InvalidSpec,
+            Error::InvalidParameter { .. } => Status::InvalidParameter,
+            Error::TooManyParameters { .. } => Status::TooManyParameters,
+            Error::MustBePositive { .. } => Status::MustBePositive,
+            Error::InvalidSession { .. } => Status::InvalidSession,
+        }
+    }
+}
+
+/// Ichimoku Kinko Hyo entry (NaN where a line has no value)
+#[repr(C)]
+#[derive(Clone, Debug)]
+pub struct IchimokuPoint {
+    pub at: u64,
+    pub conversion_line: f64,
+    pub base_line: f64,
+    pub leading_span_a: f64,
+    pub leading_span_b: f64,
+    pub lagging_span: f64,
+}
+
+unsafe fn compute<I, O: Clone>(
+    entries: *const I,
+    len: usize,
+    out: *mut O,
+    out_cap: usize,
+    out_len: *mut usize,
+    f: impl FnOnce(&[I]) -> Result<Vec<O>, Error>,
+) -> Status {
+    if out_len.is_null() || (entries.is_null() && len > 0) {
+        return Status::NullPointer;
+    }
+    let xs = if len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(entries, len)
+    };
+    let res = match f(xs) {
+        Ok(res) => res,
+        Err(e) => return Status::from(&e),
+    };
+    *out_len = res.len();
+    if res.len() > out_cap {
+        return Status::BufferTooSmall;
+    }
+    if res.is_empty() {
+        return Status::Ok;
+    }
+    if out.is_null() {
+        return Status::NullPointer;
+    }
+    slice::from_raw_parts_mut(out, res.len()).clone_from_slice(&res);
+    Status::Ok
+}
+
+/// Computes SMA (Simple Moving Average)
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_sma(
+    entries: *const IndexEntry,
+    len: usize,
+    duration: usize,
+    out: *mut IndexEntry,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> Status {
+    compute(entries, len, out, out_cap, out_len, |xs| {
+        crate::sma(xs, duration)
+    })
+}
+
+/// Computes EMA (Exponential Moving Average)
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_ema(
+    entries: *const IndexEntry,
+    len: usize,
+    duration: usize,
+    out: *mut IndexEntry,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> Status {
+    compute(entries, len, out, out_cap, out_len, |xs| {
+        crate::ema(xs, duration)
+    })
+}
+
+/// Computes WMA (Weighted Moving Average)
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_wma(
+    entries: *const IndexEntry,
+    len: usize,
+    duration: usize,
+    out: *mut IndexEntry,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> Status {
+    compute(entries, len, out, out_cap, out_len, |xs| {
+        crate::wma(xs, duration)
+    })
+}
+
+/// Computes RMA (Running Moving Average)
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_rma(
+    entries: *const IndexEntry,
+    len: usize,
+    duration: usize,
+    out: *mut IndexEntry,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> Status {
+    compute(entries, len, out, out_cap, out_len, |xs| {
+        crate::rma(xs, duration)
+    })
+}
+
+/// Computes DEMA (Double Exponential Moving Average)
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_dema(
+    entries: *const IndexEntry,
+    len: usize,
+    duration: usize,
+    out: *mut IndexEntry,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> Status {
+    compute(entries, len, out, out_cap, out_len, |xs| {
+        crate::dema(xs, duration)
+    })
+}
+
+/// Computes TEMA (Triple Exponential Moving Average)
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_tema(
+    entries: *const IndexEntry,
+    len: usize,
+    duration: usize,
+    out: *mut IndexEntry,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> Status {
+    compute(entries, len, out, out_cap, out_len, |xs| {
+        crate::tema(xs, duration)
+    })
+}
+
+/// Computes HMA (Hull Moving Average)
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_hma(
+    entries: *const IndexEntry,
+    len: usize,
+    duration: usize,
+    out: *mut IndexEntry,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> Status {
+    compute(entries, len, out, out_cap, out_len, |xs| {
+        crate::hma(xs, duration)
+    })
+}
+
+/// Computes Momentum
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_momentum(
+    entries: *const IndexEntry,
+    len: usize,
+    duration: usize,
+    out: *mut IndexEntry,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> Status {
+    compute(entries, len, out, out_cap, out_len, |xs| {
+        crate::momentum(xs, duration)
+    })
+}
+
+/// Computes RCI (Rank Correlation Index)
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_rci(
+    entries: *const IndexEntry,
+    len: usize,
+    duration: usize,
+    out: *mut IndexEntry,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> Status {
+    compute(entries, len, out, out_cap, out_len, |xs| {
+        crate::rci(xs, duration)
+    })
+}
+
+/// Computes RSI (Relative Strength Index)
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_rsi(
+    entries: *const IndexEntry,
+    len: usize,
+    duration: usize,
+    out: *mut IndexEntry,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> Status {
+    compute(entries, len, out, out_cap, out_len, |xs| {
+        crate::rsi(xs, duration)
+    })
+}
+
+/// Computes Standard Deviation
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_standard_deviation(
+    entries: *const IndexEntry,
+    len: usize,
+    duration: usize,
+    out: *mut IndexEntry,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> Status {
+    compute(entries, len, out, out_cap, out_len, |xs| {
+        crate::standard_deviation(xs, duration)
+    })
+}
+
+/// Computes Envelope
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_envelope(
+    entries: *const IndexEntry,
+    len: usize,
+    percent: f32,
+    out: *mut EnvelopeEntry,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> Status {
+    compute(entries, len, out, out_cap, out_len, |xs| {
+        crate::envelope(xs, percent)
+    })
+}
+
+/// Computes Bolinger Band
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_bolinger_band(
+    entries: *const IndexEntry,
+    len: usize,
+    duration: usize,
+    out: *mut BollingerBandEntry,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> Status {
+    compute(entries, len, out, out_cap, out_len, |xs| {
+        crate::bolinger_band(xs, duration)
+    })
+}
+
+/// Computes MACD
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_macd(
+    entries: *const IndexEntry,
+    len: usize,
+    short_duration: usize,
+    long_duration: usize,
+    signal_duration: usize,
+    out: *mut MacdEntry,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> Status {
+    compute(entries, len, out, out_cap, out_len, |xs| {
+        crate::macd(xs, short_duration, long_duration, signal_duration)
+    })
+}
+
+/// Computes ATR (Average True Range)
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_atr(
+    entries: *const Candlestick,
+    len: usize,
+    duration: usize,
+    out: *mut IndexEntry,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> Status {
+    compute(entries, len, out, out_cap, out_len, |xs| {
+        crate::atr(xs, duration)
+    })
+}
+
+/// Computes CCI (Commodity Channel Index)
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_cci(
+    entries: *const Candlestick,
+    len: usize,
+    duration: usize,
+    out: *mut IndexEntry,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> Status {
+    compute(entries, len, out, out_cap, out_len, |xs| {
+        crate::cci(xs, duration)
+    })
+}
+
+/// Computes VWMA (Volume Weighted Moving Average)
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_vwma(
+    entries: *const Candlestick,
+    len: usize,
+    duration: usize,
+    out: *mut IndexEntry,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> Status {
+    compute(entries, len, out, out_cap, out_len, |xs| {
+        crate::vwma(xs, duration)
+    })
+}
+
+/// Computes Williams %R
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_williams_percent_r(
+    entries: *const Candlestick,
+    len: usize,
+    duration: usize,
+    out: *mut IndexEntry,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> Status {
+    compute(entries, len, out, out_cap, out_len, |xs| {
+        crate::williams_percent_r(xs, duration)
+    })
+}
+
+/// Computes Awesome Oscillator
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_awesome_oscillator(
+    entries: *const Candlestick,
+    len: usize,
+    short_duration: usize,
+    long_duration: usize,
+    out: *mut IndexEntry,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> Status {
+    compute(entries, len, out, out_cap, out_len, |xs| {
+        crate::awesome_oscillator(xs, short_duration, long_duration)
+    })
+}
+
+/// Computes Parabolic SAR
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_parabolic_sar(
+    entries: *const Candlestick,
+    len: usize,
+    af_init: f32,
+    af_step: f32,
+    af_max: f32,
+    out: *mut IndexEntry,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> Status {
+    compute(entries, len, out, out_cap, out_len, |xs| {
+        crate::parabolic_sar(xs, af_init, af_step, af_max)
+    })
+}
+
+/// Computes DMI/ADX
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_dmi(
+    entries: *const Candlestick,
+    len: usize,
+    duration: usize,
+    out: *mut DmiEntry,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> Status {
+    compute(entries, len, out, out_cap, out_len, |xs| {
+        crate::dmi(xs, duration)
+    })
+}
+
+/// Computes Stochastics
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_stochastics(
+    entries: *const Candlestick,
+    len: usize,
+    duration_k: usize,
+    duration_d: usize,
+    out: *mut StochasticsEntry,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> Status {
+    compute(entries, len, out, out_cap, out_len, |xs| {
+        crate::stochastics(xs, duration_k, duration_d)
+    })
+}
+
+/// Computes Slow Stochastics
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_slow_stochastics(
+    entries: *const Candlestick,
+    len: usize,
+    duration_k: usize,
+    duration_d: usize,
+    duration_sd: usize,
+    out: *mut SlowStochasticsEntry,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> Status {
+    compute(entries, len, out, out_cap, out_len, |xs| {
+        crate::slow_stochastics(xs, duration_k, duration_d, duration_sd)
+    })
+}
+
+/// Computes Williams Fractals
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_williams_fractals(
+    entries: *const Candlestick,
+    len: usize,
+    duration: usize,
+    out: *mut WilliamsFractalsEntry,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> Status {
+    compute(entries, len, out, out_cap, out_len, |xs| {
+        crate::williams_fractals::williams_fractals_or_empty(xs, duration)
+    })
+}
+
+/// Computes Heikin Ashi
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_heikin_ashi(
+    entries: *const Candlestick,
+    len: usize,
+    out: *mut Candlestick,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> Status {
+    compute(entries, len, out, out_cap, out_len, crate::heikin_ashi)
+}
+
+/// Computes Ichimoku Kinko Hyo
+///
+/// Output entries are sorted by `at` and include future timestamps of leading spans.
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_ichimoku(
+    entries: *const Candlestick,
+    len: usize,
+    conversion_line_len: usize,
+    base_line_len: usize,
+    leading_span_b_len: usize,
+    lagging_span: usize,
+    out: *mut IchimokuPoint,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> Status {
+    compute(entries, len, out, out_cap, out_len, |xs| {
+        crate::ichimoku(
+            xs,
+            conversion_line_len,
+            base_line_len,
+            leading_span_b_len,
+            lagging_span,
+        )
+        .map(|data| ichimoku_points(&data))
+    })
+}
+
+fn ichimoku_points(data: &IchimokuData) -> Vec<IchimokuPoint> {
+    let mut ats: Vec<u64> = [
+        &data.conversion_line,
+        &data.base_line,
+        &data.leading_span_a,
+        &data.leading_span_b,
+        &data.lagging_span,
+    ]
+    .iter()
+    .flat_map(|xs| xs.iter().map(|x| x.get_at()))
+    .collect();
+    ats.sort();
+    ats.dedup();
+    ats.iter()
+        .filter_map(|at| data.get(*at))
+        .map(|x| IchimokuPoint {
+            at: x.at,
+            conversion_line: x.conversion_line.unwrap_or(f64::NAN),
+            base_line: x.base_line.unwrap_or(f64::NAN),
+            leading_span_a: x.leading_span_a.unwrap_or(f64::NAN),
+            leading_span_b: x.leading_span_b.unwrap_or(f64::NAN),
+            lagging_span: x.lagging_span.unwrap_or(f64::NAN),
+        })
+        .collect()
+}
+
+/// Single value indicator computed by [`Stream`]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StreamKind {
+    Sma,
+    Ema,
+    Wma,
+    Rma,
+    Dema,
+    Tema,
+    Hma,
+    Momentum,
+    Rci,
+    Rsi,
+    StandardDeviation,
+    Atr,
+    Cci,
+    Vwma,
+    WilliamsPercentR,
+}
+
+impl StreamKind {
+    /// Returns the number of latest candlesticks needed to compute the latest value,
+    /// or None if the indicator depends on the whole history
+    fn window(&self, duration: usize) -> Option<usize> {
+        match self {
+            StreamKind::Ema
+            | StreamKind::Rma
+            | StreamKind::Dema
+            | StreamKind::Tema
+            | StreamKind::Rsi
+            | StreamKind::Atr => None,
+            StreamKind::Hma => {
+                Some(duration + ((duration as f32).sqrt() as usize).saturating_sub(1))
+            }
+            StreamKind::Momentum => Some(duration + 1),
+            _ => Some(duration),
+        }
+    }
+
+    fn calc(&self, xs: &[Candlestick], duration: usize) -> Result<Vec<IndexEntry>, Error> {
+        match self {
+            StreamKind::Sma => crate::sma(xs, duration),
+            StreamKind::Wma => crate::wma(xs, duration),
+            StreamKind::Hma => crate::hma(xs, duration),
+            StreamKind::Momentum => crate::momentum(xs, duration),
+            StreamKind::Rci => crate::rci(xs, duration),
+            StreamKind::StandardDeviation => crate::standard_deviation(xs, duration),
+            StreamKind::Cci => crate::cci(xs, duration),
+            StreamKind::Vwma => crate::vwma(xs, duration),
+            StreamKind::WilliamsPercentR => crate::williams_percent_r(xs, duration),
+            _ => Ok(vec![]),
+        }
+    }
+
+    fn initial_state(&self, duration: usize) -> StreamState {
+        let ema = || EmaState::new(duration, 2.0 / ((duration as f64) + 1.0));
+        let rma = || EmaState::new(duration, 1.0 / (duration as f64));
+        match self {
+            StreamKind::Ema => StreamState::Emas(vec![ema()]),
+            StreamKind::Rma => StreamState::Emas(vec![rma()]),
+            StreamKind::Dema => StreamState::Emas(vec![ema(), ema()]),
+            StreamKind::Tema => StreamState::Emas(vec![ema(), ema(), ema()]),
+            StreamKind::Rsi => StreamState::Rsi(RsiState {
+                duration,
+                count: 0,
+                prev: 0.0,
+                upside: 0.0,
+                downside: 0.0,
+            }),
+            StreamKind::Atr => StreamState::Atr(None, rma()),
+            _ => StreamState::Window(VecDeque::new()),
+        }
+    }
+}
+
+/// EMA state updated value by value, matching [`crate::ema`] and [`crate::rma`]
+#[derive(Clone, Debug)]
+struct EmaState {
+    duration: usize,
+    alpha: f64,
+    count: usize,
+    value: f64,
+}
+
+impl EmaState {
+    fn new(duration: usize, alpha: f64) -> Self {
+        EmaState {
+            duration,
+            alpha,
+            count: 0,
+            value: 0.0,
+        }
+    }
+
+    /// Returns EMA at `x`, or None while it is seeded with SMA of the first values
+    fn update(&mut self, x: f64) -> Option<f64> {
+        if self.duration == 0 {
+            return None;
+        }
+        self.count += 1;
+        if self.count < self.duration {
+            self.value += x;
+            None
+        } else if self.count == self.duration {
+            self.value = (self.value + x) / (self.duration as f64);
+            None
+        } else {
+            self.value += self.alpha * (x - self.value);
+            Some(self.value)
+        }
+    }
+}
+
+/// RSI state updated value by value, matching [`crate::rsi`]
+#[derive(Clone, Debug)]
+struct RsiState {
+    duration: usize,
+    count: usize,
+    prev: f64,
+    upside: f64,
+    downside: f64,
+}
+
+impl RsiState {
+    fn update(&mut self, x: f64) -> Option<f64> {
+        if self.duration == 0 {
+            return None;
+        }
+        let d = self.duration as f64;
+        self.count += 1;
+        if self.count <= self.duration + 1 {
+            // sums of moves until the first RSI
+            if self.count > 1 {
+                if self.prev < x {
+                    self.upside += x - self.prev;
+                } else if self.prev > x {
+                    self.downside += self.prev - x;
+                }
+            }
+            if self.count == self.duration + 1 {
+                self.upside /= d;
+                self.downside /= d;
+            }
+        } else {
+            self.upside = (self.upside * (d - 1.0) + (x - self.prev).max(0.0)) / d;
+            self.downside = (self.downside * (d - 1.0) + (self.prev - x).max(0.0)) / d;
+        }
+        self.prev = x;
+        (self.count > self.duration).then(|| self.upside / (self.upside + self.downside) * 100.0)
+    }
+}
+
+/// State of [`Stream`] after the candlesticks pushed so far
+#[derive(Clone, Debug)]
+enum StreamState {
+    /// The latest candlesticks recomputed on each push
+    Window(VecDeque<Candlestick>),
+    /// EMAs chained one over another, as in DEMA and TEMA
+    Emas(Vec<EmaState>),
+    Rsi(RsiState),
+    /// Previous close and RMA of true range
+    Atr(Option<f64>, EmaState),
+}
+
+impl StreamState {
+    /// Adds a candlestick whose value has already been returned
+    fn commit(&mut self, kind: StreamKind, duration: usize, entry: &Candlestick) {
+        match self {
+            StreamState::Window(xs) => push_window(xs, kind.window(duration), entry),
+            _ => {
+                let _ = self.update(kind, duration, entry);
+            }
+        }
+    }
+
+    /// Adds a candlestick and returns the indicator value at it
+    fn update(
+        &mut self,
+        kind: StreamKind,
+        duration: usize,
+        entry: &Candlestick,
+    ) -> Result<Option<f64>, Error> {
+        match self {
+            StreamState::Window(xs) => {
+                push_window(xs, kind.window(duration), entry);
+                let res = kind.calc(xs.make_contiguous(), duration)?;
+                Ok(res.last().filter(|x| x.at == entry.at).map(|x| x.value))
+            }
+            StreamState::Emas(emas) => {
+                let mut value = Some(entry.close);
+                let values: Vec<f64> = emas
+                    .iter_mut()
+                    .map_while(|ema| {
+                        value = value.and_then(|x| ema.update(x));
+                        value
+                    })
+                    .collect();
+                Ok(match values[..] {
+                    _ if values.len() < emas.len() => None,
+                    [e1] => Some(e1),
+                    [e1, e2] => Some(e1 * 2.0 - e2),
+                    [e1, e2, e3] => Some(e1 * 3.0 - e2 * 3.0 + e3),
+                    _ => None,
+                })
+            }
+            StreamState::Rsi(rsi) => Ok(rsi.update(entry.close)),
+            StreamState::Atr(prev_close, rma) => {
+                let value = prev_close.and_then(|prev| {
+                    let r1 = entry.high - prev;
+                    let r2 = (entry.low - prev).abs();
+                    let r3 = (entry.high - entry.low).abs();
+                    rma.update(r1.max(r2).max(r3))
+                });
+                *prev_close = Some(entry.close);
+                Ok(value)
+            }
+        }
+    }
+}
+
+fn push_window(xs: &mut VecDeque<Candlestick>, len: Option<usize>, entry: &Candlestick) {
+    xs.push_back(entry.clone());
+    while xs.len() > len.unwrap_or(0) {
+        xs.pop_front();
+    }
+}
+
+/// Opaque handle accumulating candlesticks for a single value indicator
+///
+/// Series indicators are computed over close prices.
+/// Recursive indicators such as EMA, RSI and ATR keep their state, and the others
+/// keep only the latest candlesticks they need, so each push takes constant time
+/// in the number of candlesticks pushed so far.
+pub struct Stream {
+    kind: StreamKind,
+    duration: usize,
+    /// State before the last candlestick, which may still be replaced
+    state: StreamState,
+    last: Option<Candlestick>,
+    len: usize,
+}
+
+/// Creates new Stream, which must be released by [`m4rs_stream_free`]
+#[no_mangle]
+pub extern "C" fn m4rs_stream_new(kind: StreamKind, duration: usize) -> *mut Stream {
+    Box::into_raw(Box::new(Stream {
+        kind,
+        duration,
+        state: kind.initial_state(duration),
+        last: None,
+        len: 0,
+    }))
+}
+
+/// Pushes a candlestick and writes the latest indicator value to `out`
+///
+/// A candlestick with the same `at` as the last one replaces it, so an
+/// unfinished bar can be updated repeatedly.
+/// Returns [`Status::NotReady`] until enough entries have been pushed.
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_stream_push(
+    stream: *mut Stream,
+    entry: *const Candlestick,
+    out: *mut f64,
+) -> Status {
+    if stream.is_null() || entry.is_null() || out.is_null() {
+        return Status::NullPointer;
+    }
+    let stream = &mut *stream;
+    let entry = &*entry;
+    if let Err(e) = Candlestick::validate_list(slice::from_ref(entry)) {
+        return Status::from(&e);
+    }
+    match &stream.last {
+        Some(last) if last.at > entry.at => return Status::OutOfOrder,
+        Some(last) if last.at < entry.at => {
+            stream.state.commit(stream.kind, stream.duration, last);
+            stream.len += 1;
+        }
+        _ => {}
+    }
+    stream.last = Some(entry.clone());
+    let mut state = stream.state.clone();
+    match state.update(stream.kind, stream.duration, entry) {
+        Ok(Some(x)) => {
+            *out = x;
+            Status::Ok
+        }
+        Ok(None) => Status::NotReady,
+        Err(e) => Status::from(&e),
+    }
+}
+
+/// Returns the number of candlesticks pushed to the stream
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_stream_len(stream: *const Stream) -> usize {
+    if stream.is_null() {
+        return 0;
+    }
+    (*stream).len + usize::from((*stream).last.is_some())
+}
+
+/// Releases Stream created by [`m4rs_stream_new`]
+///
+/// # Safety
+/// See [module documentation](self).
+#[no_mangle]
+pub unsafe extern "C" fn m4rs_stream_free(stream: *mut Stream) {
+    if !stream.is_null() {
+        drop(Box::from_raw(stream));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_into_buffer() {
+        let xs = [
+            IndexEntry::new(1719400001, 110.0),
+            IndexEntry::new(1719400002, 130.0),
+            IndexEntry::new(1719400003, 120.0),
+        ];
+        let mut out = vec![IndexEntry::new(0, 0.0); 2];
+        let mut out_len = 0;
+        let status =
+            unsafe { m4rs_sma(xs.as_ptr(), xs.len(), 2, out.as_mut_ptr(), 2, &mut out_len) };
+        assert_eq!(Status::Ok, status);
+        assert_eq!(2, out_len);
+        assert_eq!(1719400002, out[0].at);
+        assert_eq!(120.0, out[0].value);
+        assert_eq!(1719400003, out[1].at);
+        assert_eq!(125.0, out[1].value);
+
+        let status =
+            unsafe { m4rs_sma(xs.as_ptr(), xs.len(), 1, out.as_mut_ptr(), 2, &mut out_len) };
+        assert_eq!(Status::BufferTooSmall, status);
+        assert_eq!(3, out_len);
+
+        let ys = [IndexEntry::new(1719400001, f64::NAN)];
+        let status =
+            unsafe { m4rs_sma(ys.as_ptr(), ys.len(), 1, out.as_mut_ptr(), 2, &mut out_len) };
+        assert_eq!(Status::ContainsNan, status);
+    }
+
+    #[test]
+    fn test_stream() {
+        let stream = m4rs_stream_new(StreamKind::Sma, 2);
+        let mut out = 0.0;
+        let push = |at: u64, close: f64, out: &mut f64| unsafe {
+            let x = Candlestick::new(at, close, close, close, close, 1000.0);
+            m4rs_stream_push(stream, &x, out)
+        };
+        assert_eq!(Status::NotReady, push(1719400001, 110.0, &mut out));
+        assert_eq!(Status::Ok, push(1719400002, 130.0, &mut out));
+        assert_eq!(120.0, out);
+        assert_eq!(Status::Ok, push(1719400002, 150.0, &mut out));
+        assert_eq!(130.0, out);
+        assert_eq!(Status::OutOfOrder, push(1719400001, 150.0, &mut out));
+        assert_eq!(2, unsafe { m4rs_stream_len(stream) });
+        unsafe { m4rs_stream_free(stream) };
+    }
+    #[test]
+    fn test_stream_matches_batch() {
+        let xs = vec![
+            Candlestick::new(1719400001, 98.0, 104.0, 95.0, 100.0, 1000.0),
+            Candlestick::new(1719400002, 118.0, 124.0, 115.0, 120.0, 1000.0),
+            Candlestick::new(1719400003, 99.0, 105.0, 96.0, 101.0, 1000.0),
+            Candlestick::new(1719400004, 119.0, 125.0, 116.0, 121.0, 1000.0),
+            Candlestick::new(1719400005, 100.0, 106.0, 97.0, 102.0, 1000.0),
+            Candlestick::new(1719400006, 125.0, 131.0, 122.0, 127.0, 1000.0),
+            Candlestick::new(1719400007, 106.0, 112.0, 103.0, 108.0, 1000.0),
+            Candlestick::new(1719400008, 126.0, 132.0, 123.0, 128.0, 1000.0),
+            Candlestick::new(1719400009, 107.0, 113.0, 104.0, 109.0, 1000.0),
+            Candlestick::new(1719400010, 127.0, 133.0, 124.0, 129.0, 1000.0),
+            Candlestick::new(1719400011, 113.0, 119.0, 110.0, 115.0, 1000.0),
+            Candlestick::new(1719400012, 133.0, 139.0, 130.0, 135.0, 1000.0),
+            Candlestick::new(1719400013, 114.0, 120.0, 111.0, 116.0, 1000.0),
+            Candlestick::new(1719400014, 95.0, 101.0, 92.0, 97.0, 1000.0),
+            Candlestick::new(1719400015, 115.0, 121.0, 112.0, 117.0, 1000.0),
+            Candlestick::new(1719400016, 101.0, 107.0, 98.0, 103.0, 1000.0),
+            Candlestick::new(1719400017, 121.0, 127.0, 118.0, 123.0, 1000.0),
+            Candlestick::new(1719400018, 102.0, 108.0, 99.0, 104.0, 1000.0),
+            Candlestick::new(1719400019, 122.0, 128.0, 119.0, 124.0, 1000.0),
+            Candlestick::new(1719400020, 103.0, 109.0, 100.0, 105.0, 1000.0),
+            Candlestick::new(1719400021, 128.0, 134.0, 125.0, 130.0, 1000.0),
+            Candlestick::new(1719400022, 109.0, 115.0, 106.0, 111.0, 1000.0),
+            Candlestick::new(1719400023, 129.0, 135.0, 126.0, 131.0, 1000.0),
+            Candlestick::new(1719400024, 110.0, 116.0, 107.0, 112.0, 1000.0),
+            Candlestick::new(1719400025, 130.0, 136.0, 127.0, 132.0, 1000.0),
+            Candlestick::new(1719400026, 116.0, 122.0, 113.0, 118.0, 1000.0),
+            Candlestick::new(1719400027, 97.0, 103.0, 94.0, 99.0, 1000.0),
+            Candlestick::new(1719400028, 117.0, 123.0, 114.0, 119.0, 1000.0),
+            Candlestick::new(1719400029, 98.0, 104.0, 95.0, 100.0, 1000.0),
+            Candlestick::new(1719400030, 118.0, 124.0, 115.0, 120.0, 1000.0),
+            Candlestick::new(1719400031, 104.0, 110.0, 101.0, 106.0, 1000.0),
+            Candlestick::new(1719400032, 124.0, 130.0, 121.0, 126.0, 1000.0),
+            Candlestick::new(1719400033, 105.0, 111.0, 102.0, 107.0, 1000.0),
+            Candlestick::new(1719400034, 125.0, 131.0, 122.0, 127.0, 1000.0),
+            Candlestick::new(1719400035, 106.0, 112.0, 103.0, 108.0, 1000.0),
+            Candlestick::new(1719400036, 131.0, 137.0, 128.0, 133.0, 1000.0),
+            Candlestick::new(1719400037, 112.0, 118.0, 109.0, 114.0, 1000.0),
+            Candlestick::new(1719400038, 132.0, 138.0, 129.0, 134.0, 1000.0),
+            Candlestick::new(1719400039, 113.0, 119.0, 110.0, 115.0, 1000.0),
+            Candlestick::new(1719400040, 94.0, 100.0, 91.0, 96.0, 1000.0),
+        ];
+        let kinds = [
+            StreamKind::Sma,
+            StreamKind::Ema,
+            StreamKind::Wma,
+            StreamKind::Rma,
+            StreamKind::Dema,
+            StreamKind::Tema,
+            StreamKind::Hma,
+            StreamKind::Momentum,
+            StreamKind::Rci,
+            StreamKind::Rsi,
+            StreamKind::StandardDeviation,
+            StreamKind::Atr,
+            StreamKind::Cci,
+            StreamKind::Vwma,
+            StreamKind::WilliamsPercentR,
+        ];
+        for kind in kinds {
+            let stream = m4rs_stream_new(kind, 9);
+            let mut out = 0.0;
+            for (i, x) in xs.iter().enumerate() {
+                // the latest value of the whole calculation over entries so far
+                let expected = match kind {
+                    StreamKind::Ema => crate::ema(&xs[..=i], 9),
+                    StreamKind::Rma => crate::rma(&xs[..=i], 9),
+                    StreamKind::Dema => crate::dema(&xs[..=i], 9),
+                    StreamKind::Tema => crate::tema(&xs[..=i], 9),
+                    StreamKind::Rsi => crate::rsi(&xs[..=i], 9),
+                    StreamKind::Atr => crate::atr(&xs[..=i], 9),
+                    _ => kind.calc(&xs[..=i], 9),
+                }
+                .unwrap()
+                .pop()
+                .filter(|e| e.at == x.at);
+
+                // an unfinished bar pushed first is replaced by the final one
+                let unfinished = Candlestick::new(x.at, x.open, x.high, x.low, x.open, 500.0);
+                unsafe { m4rs_stream_push(stream, &unfinished, &mut out) };
+                let status = unsafe { m4rs_stream_push(stream, x, &mut out) };
+                match expected {
+                    Some(e) => {
+                        assert_eq!(Status::Ok, status, "{:?}", kind);
+                        assert_eq!(e.value, out, "{:?} at {}", kind, x.at);
+                    }
+                    None => assert_eq!(Status::NotReady, status, "{:?}", kind),
+                }
+            }
+            assert_eq!(xs.len(), unsafe { m4rs_stream_len(stream) });
+            unsafe { m4rs_stream_free(stream) };
+        }
+    }
+}
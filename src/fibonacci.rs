@@ -0,0 +1,91 @@
+//! Fibonacci Retracement and Extension
+//!
+//! # Examples
+//! ```rust
+//! // Retracement levels of the swing from 100 up to 150
+//! let levels = m4rs::fibonacci_retracement(100.0, 150.0);
+//! assert_eq!((0.5, 125.0), (levels[3].ratio, levels[3].value));
+//!
+//! // Extension levels of the swing from 150 down to 100
+//! let levels = m4rs::fibonacci_extension(150.0, 100.0);
+//! ```
+
+use std::fmt::Display;
+
+/// Ratios of Fibonacci retracement from 0 (end of the swing) to 1 (start of the swing)
+pub const FIBONACCI_RETRACEMENT_RATIOS: [f64; 7] = [0.0, 0.236, 0.382, 0.5, 0.618, 0.786, 1.0];
+
+/// Ratios of Fibonacci extension beyond the end of the swing
+pub const FIBONACCI_EXTENSION_RATIOS: [f64; 5] = [1.272, 1.414, 1.618, 2.0, 2.618];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FibonacciLevel {
+    pub ratio: f64,
+    pub value: f64,
+}
+
+impl Display for FibonacciLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "FibonacciLevel(ratio={} value={})",
+            self.ratio, self.value
+        )
+    }
+}
+
+/// Returns retracement levels of the swing from `start` to `end`, going back from `end` by ratios
+pub fn fibonacci_retracement(start: f64, end: f64) -> Vec<FibonacciLevel> {
+    FIBONACCI_RETRACEMENT_RATIOS
+        .iter()
+        .map(|ratio| FibonacciLevel {
+            ratio: *ratio,
+            value: end - (end - start) * ratio,
+        })
+        .collect()
+}
+
+/// Returns extension levels of the swing from `start` to `end`, projecting the swing from `start` by ratios
+pub fn fibonacci_extension(start: f64, end: f64) -> Vec<FibonacciLevel> {
+    FIBONACCI_EXTENSION_RATIOS
+        .iter()
+        .map(|ratio| FibonacciLevel {
+            ratio: *ratio,
+            value: start + (end - start) * ratio,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_fibonacci_retracement() {
+        let got: Vec<f64> = super::fibonacci_retracement(100.0, 150.0)
+            .iter()
+            .map(|x| x.value)
+            .collect();
+        let expected = [150.0, 138.2, 130.9, 125.0, 119.1, 110.7, 100.0];
+        for (x, y) in expected.iter().zip(&got) {
+            assert!((x - y).abs() < 1e-9, "{} != {}", x, y);
+        }
+
+        // downswing
+        let got = super::fibonacci_retracement(150.0, 100.0);
+        assert!((got[2].value - 119.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fibonacci_extension() {
+        let got: Vec<f64> = super::fibonacci_extension(100.0, 150.0)
+            .iter()
+            .map(|x| x.value)
+            .collect();
+        let expected = [163.6, 170.7, 180.9, 200.0, 230.9];
+        for (x, y) in expected.iter().zip(&got) {
+            assert!((x - y).abs() < 1e-9, "{} != {}", x, y);
+        }
+
+        let got = super::fibonacci_extension(150.0, 100.0);
+        assert!((got[2].value - 69.1).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,65 @@
+//! Force Index
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get Force Index calculation result
+//! let result = m4rs::force_index(&candlesticks, 13);
+//! ```
+
+use crate::{ema, Candlestick, Error, IndexEntry};
+
+/// Returns Force Index, EMA of close change multiplied by volume, for given Candlestick list
+pub fn force_index(entries: &[Candlestick], duration: usize) -> Result<Vec<IndexEntry>, Error> {
+    if duration == 0 || entries.len() <= duration {
+        return Ok(vec![]);
+    }
+    Candlestick::validate_list(entries)?;
+
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.at);
+
+    let raw: Vec<IndexEntry> = sorted
+        .windows(2)
+        .map(|xs| IndexEntry {
+            at: xs[1].at,
+            value: (xs[1].close - xs[0].close) * xs[1].volume,
+        })
+        .collect();
+    ema(&raw, duration)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Candlestick;
+
+    #[test]
+    fn test_force_index() {
+        let xs = vec![
+            Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1200.0),
+            Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 800.0),
+            Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1500.0),
+            Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 900.0),
+        ];
+        let got = super::force_index(&xs, 1).unwrap();
+        assert_eq!(
+            vec![-8000.0, -37500.0, -11700.0],
+            got.iter().map(|x| x.value).collect::<Vec<f64>>()
+        );
+
+        let got = super::force_index(&xs, 2).unwrap();
+        assert_eq!(2, got.len());
+        assert_eq!(1719400004, got[0].at);
+        assert!((got[0].value - -22333.33333333333).abs() < 1e-9);
+        assert!((got[1].value - -15244.44444444444).abs() < 1e-9);
+    }
+}
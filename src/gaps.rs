@@ -0,0 +1,217 @@
+//! Detection and repair of missing bars in Candlestick list
+//!
+//! # Examples
+//! ```rust
+//! // Prepare hourly candlesticks in some way, missing the 3rd one
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719403200, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719406800, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719414000, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//! ];
+//!
+//! // Find missing bars of a 24/7 market
+//! let report = m4rs::find_gaps(&candlesticks, 3600, &m4rs::Continuous).unwrap();
+//! assert_eq!(vec![1719410400], report.missing);
+//!
+//! // Fill missing bars with close of the previous bar before calculating indicators
+//! let repaired =
+//!     m4rs::fill_gaps(&candlesticks, 3600, &m4rs::Continuous, m4rs::FillMethod::Flat).unwrap();
+//! let result = m4rs::sma(&repaired, 3);
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{Candlestick, Error, TradingCalendar};
+
+/// Timestamps of Candlestick list not matching bars expected by a calendar
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GapReport {
+    /// Expected bars not in the list
+    pub missing: Vec<u64>,
+    /// Bars appearing more than once, listed once each
+    pub duplicates: Vec<u64>,
+    /// Bars in the list which are not expected, such as misaligned or out of sessions
+    pub unexpected: Vec<u64>,
+}
+
+impl GapReport {
+    /// Returns true if the list matches expected bars
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.duplicates.is_empty() && self.unexpected.is_empty()
+    }
+}
+
+/// Values of synthesised bars
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FillMethod {
+    /// Open, high, low and close at close of the previous bar with zero volume
+    Flat,
+    /// Copy of the previous bar
+    ForwardFill,
+}
+
+/// Returns missing, duplicate and unexpected timestamps of given Candlestick list
+///
+/// Expected bars are those of `interval` seconds by `calendar` from the first Candlestick to the last.
+pub fn find_gaps(
+    entries: &[Candlestick],
+    interval: u64,
+    calendar: &impl TradingCalendar,
+) -> Result<GapReport, Error> {
+    if entries.is_empty() {
+        return Ok(GapReport::default());
+    }
+    Candlestick::validate_list(entries)?;
+
+    let mut counts = BTreeMap::<u64, usize>::new();
+    for x in entries {
+        *counts.entry(x.at).or_default() += 1;
+    }
+    let expected = expected_bars(&counts, interval, calendar);
+    Ok(GapReport {
+        missing: expected
+            .iter()
+            .filter(|x| !counts.contains_key(x))
+            .cloned()
+            .collect(),
+        duplicates: counts
+            .iter()
+            .filter(|(_, n)| **n > 1)
+            .map(|(at, _)| *at)
+            .collect(),
+        unexpected: counts
+            .keys()
+            .filter(|x| !expected.contains(x))
+            .cloned()
+            .collect(),
+    })
+}
+
+/// Returns given Candlestick list sorted with missing bars filled by `method`
+///
+/// The last one of duplicate Candlesticks is kept, and unexpected ones are kept as they are.
+pub fn fill_gaps(
+    entries: &[Candlestick],
+    interval: u64,
+    calendar: &impl TradingCalendar,
+    method: FillMethod,
+) -> Result<Vec<Candlestick>, Error> {
+    if entries.is_empty() {
+        return Ok(vec![]);
+    }
+    Candlestick::validate_list(entries)?;
+
+    let mut bars = BTreeMap::<u64, Candlestick>::new();
+    for x in entries {
+        bars.insert(x.at, x.clone());
+    }
+    let missing: Vec<u64> = expected_bars(&bars, interval, calendar)
+        .into_iter()
+        .filter(|x| !bars.contains_key(x))
+        .collect();
+    for at in missing {
+        // the first bar is always in the list, so there is a previous one
+        let prev = bars.range(..at).next_back().unwrap().1;
+        let filled = match method {
+            FillMethod::Flat => {
+                Candlestick::new(at, prev.close, prev.close, prev.close, prev.close, 0.0)
+            }
+            FillMethod::ForwardFill => Candlestick { at, ..prev.clone() },
+        };
+        bars.insert(at, filled);
+    }
+    Ok(bars.into_values().collect())
+}
+
+fn expected_bars<T>(
+    bars: &BTreeMap<u64, T>,
+    interval: u64,
+    calendar: &impl TradingCalendar,
+) -> BTreeSet<u64> {
+    let (first, last) = (
+        *bars.keys().next().unwrap(),
+        *bars.keys().next_back().unwrap(),
+    );
+    calendar.bars(first, last, interval).into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FillMethod, GapReport};
+    use crate::{Candlestick, Continuous, SessionCalendar};
+
+    fn candlesticks() -> Vec<Candlestick> {
+        // daily candlesticks in UTC from Wednesday 2024-06-26 to Tuesday 2024-07-02,
+        // with Thursday twice and Saturday, missing Friday
+        vec![
+            Candlestick::new(1719360000, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719446400, 110.0, 140.0, 100.0, 130.0, 1000.0),
+            Candlestick::new(1719446400, 110.0, 140.0, 100.0, 125.0, 2000.0),
+            Candlestick::new(1719792000, 130.0, 135.0, 120.0, 120.0, 1000.0),
+            Candlestick::new(1719619200, 120.0, 130.0, 80.0, 95.0, 1000.0),
+            Candlestick::new(1719878400, 90.0, 100.0, 70.0, 82.0, 1000.0),
+        ]
+    }
+
+    #[test]
+    fn test_find_gaps() {
+        let got = super::find_gaps(&candlesticks(), 86400, &SessionCalendar::weekdays(0)).unwrap();
+        assert_eq!(
+            GapReport {
+                missing: vec![1719532800],
+                duplicates: vec![1719446400],
+                unexpected: vec![1719619200],
+            },
+            got
+        );
+        assert!(!got.is_empty());
+
+        // Friday and Sunday are missing in 24/7 market
+        let got = super::find_gaps(&candlesticks(), 86400, &Continuous).unwrap();
+        assert_eq!(vec![1719532800, 1719705600], got.missing);
+        assert!(got.unexpected.is_empty());
+
+        assert!(super::find_gaps(&[], 86400, &Continuous)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_fill_gaps() {
+        let calendar = SessionCalendar::weekdays(0);
+        let got = super::fill_gaps(&candlesticks(), 86400, &calendar, FillMethod::Flat).unwrap();
+        let ats: Vec<u64> = got.iter().map(|x| x.at).collect();
+        assert_eq!(
+            vec![1719360000, 1719446400, 1719532800, 1719619200, 1719792000, 1719878400],
+            ats
+        );
+        // the last duplicate is kept
+        assert_eq!(125.0, got[1].close);
+        let filled = &got[2];
+        assert_eq!(
+            (125.0, 125.0, 125.0, 125.0, 0.0),
+            (
+                filled.open,
+                filled.high,
+                filled.low,
+                filled.close,
+                filled.volume
+            )
+        );
+
+        let got =
+            super::fill_gaps(&candlesticks(), 86400, &calendar, FillMethod::ForwardFill).unwrap();
+        let filled = &got[2];
+        assert_eq!(1719532800, filled.at);
+        assert_eq!(
+            (110.0, 140.0, 100.0, 125.0, 2000.0),
+            (
+                filled.open,
+                filled.high,
+                filled.low,
+                filled.close,
+                filled.volume
+            )
+        );
+    }
+}
@@ -21,7 +21,7 @@ pub fn heikin_ashi(entries: &[Candlestick]) -> Result<Vec<Candlestick>, Error> {
     Candlestick::validate_list(entries)?;
 
     let mut sorted = entries.to_owned();
-    sorted.sort_by(|a, b| a.at.cmp(&b.at));
+    sorted.sort_by_key(|x| x.at);
 
     Ok(sorted.iter().fold(vec![], |z, x| {
         if z.is_empty() {
@@ -0,0 +1,323 @@
+//! Historical Volatility
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get 20 bars close-to-close volatility annualised for daily candlesticks
+//! let result = m4rs::historical_volatility(
+//!     &candlesticks,
+//!     20,
+//!     m4rs::VolatilityEstimator::CloseToClose,
+//!     252.0,
+//! );
+//!
+//! // Get 20 bars Yang-Zhang volatility annualised for hourly candlesticks of 24/7 market
+//! let result = m4rs::historical_volatility(
+//!     &candlesticks,
+//!     20,
+//!     m4rs::VolatilityEstimator::YangZhang,
+//!     365.0 * 24.0,
+//! );
+//! ```
+
+use std::f64::consts::LN_2;
+
+use crate::{Candlestick, Error, IndexEntry};
+
+/// Estimator of variance used by historical volatility
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VolatilityEstimator {
+    /// Sample variance of log returns of close
+    CloseToClose,
+    /// Range of high and low
+    Parkinson,
+    /// Range of high and low, and log return from open to close
+    GarmanKlass,
+    /// Ranges of high and low from open and close, unbiased by drift
+    RogersSatchell,
+    /// Overnight (previous close to open) and open-to-close variances combined with Rogers-Satchell
+    YangZhang,
+}
+
+/// Returns annualised historical volatility of `duration` bars for given Candlestick list
+///
+/// Volatility is the square root of the variance per bar by `estimator` multiplied by
+/// `periods_per_year`, e.g. 252 for daily bars of stock market, as a fraction (0.2 for 20%).
+/// Close-to-close and Yang-Zhang need the close of the bar before the window, so they
+/// start one bar later than the others. `duration` must be 2 or more.
+pub fn historical_volatility(
+    entries: &[Candlestick],
+    duration: usize,
+    estimator: VolatilityEstimator,
+    periods_per_year: f32,
+) -> Result<Vec<IndexEntry>, Error> {
+    if duration < 2 || entries.len() < duration {
+        return Ok(vec![]);
+    }
+    Candlestick::validate_list(entries)?;
+    if !periods_per_year.is_finite() || periods_per_year <= 0.0 {
+        return Err(Error::MustBePositiveF32 {
+            value: periods_per_year,
+            field: "periods_per_year".to_string(),
+        });
+    }
+    validate_prices(entries)?;
+
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.at);
+
+    let n = duration as f64;
+    let variances = match estimator {
+        VolatilityEstimator::CloseToClose => {
+            let returns: Vec<f64> = sorted
+                .windows(2)
+                .map(|xs| (xs[1].close / xs[0].close).ln())
+                .collect();
+            sorted[1..]
+                .windows(duration)
+                .zip(returns.windows(duration))
+                .map(|(xs, rs)| (xs.last().unwrap().at, sample_variance(rs)))
+                .collect()
+        }
+        VolatilityEstimator::Parkinson => mean_of(&sorted, duration, |x| {
+            (x.high / x.low).ln().powi(2) / (4.0 * LN_2)
+        }),
+        VolatilityEstimator::GarmanKlass => mean_of(&sorted, duration, |x| {
+            0.5 * (x.high / x.low).ln().powi(2)
+                - (2.0 * LN_2 - 1.0) * (x.close / x.open).ln().powi(2)
+        }),
+        VolatilityEstimator::RogersSatchell => mean_of(&sorted, duration, rogers_satchell),
+        VolatilityEstimator::YangZhang => {
+            let k = 0.34 / (1.34 + (n + 1.0) / (n - 1.0));
+            sorted
+                .windows(duration + 1)
+                .map(|xs| {
+                    let overnight: Vec<f64> = xs
+                        .windows(2)
+                        .map(|p| (p[1].open / p[0].close).ln())
+                        .collect();
+                    let open_to_close: Vec<f64> =
+                        xs[1..].iter().map(|x| (x.close / x.open).ln()).collect();
+                    let rs = xs[1..].iter().map(rogers_satchell).sum::<f64>() / n;
+                    (
+                        xs.last().unwrap().at,
+                        sample_variance(&overnight)
+                            + k * sample_variance(&open_to_close)
+                            + (1.0 - k) * rs,
+                    )
+                })
+                .collect()
+        }
+    };
+
+    let periods_per_year = periods_per_year as f64;
+    Ok(variances
+        .into_iter()
+        .map(|(at, variance): (u64, f64)| IndexEntry {
+            at,
+            value: (variance * periods_per_year).sqrt(),
+        })
+        .collect())
+}
+
+fn validate_prices(entries: &[Candlestick]) -> Result<(), Error> {
+    for x in entries {
+        for (field, value) in [
+            ("open", x.open),
+            ("high", x.high),
+            ("low", x.low),
+            ("close", x.close),
+        ] {
+            if value <= 0.0 {
+                return Err(Error::MustBePositive {
+                    at: x.at,
+                    field: field.to_string(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+fn mean_of(
+    sorted: &[Candlestick],
+    duration: usize,
+    f: impl Fn(&Candlestick) -> f64,
+) -> Vec<(u64, f64)> {
+    let values: Vec<f64> = sorted.iter().map(f).collect();
+    sorted
+        .windows(duration)
+        .zip(values.windows(duration))
+        .map(|(xs, vs)| {
+            (
+                xs.last().unwrap().at,
+                vs.iter().sum::<f64>() / duration as f64,
+            )
+        })
+        .collect()
+}
+
+fn rogers_satchell(x: &Candlestick) -> f64 {
+    (x.high / x.close).ln() * (x.high / x.open).ln()
+        + (x.low / x.close).ln() * (x.low / x.open).ln()
+}
+
+fn sample_variance(values: &[f64]) -> f64 {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VolatilityEstimator;
+    use crate::{Candlestick, Error};
+
+    fn candlesticks() -> Vec<Candlestick> {
+        vec![
+            Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+            Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+            Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+            Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+            Candlestick::new(1719400006, 82.0, 120.0, 80.0, 115.0, 1000.0),
+            Candlestick::new(1719400007, 118.0, 150.0, 110.0, 145.0, 1000.0),
+            Candlestick::new(1719400008, 145.0, 160.0, 140.0, 155.0, 1000.0),
+            Candlestick::new(1719400009, 155.0, 158.0, 145.0, 150.0, 1000.0),
+            Candlestick::new(1719400010, 150.0, 165.0, 148.0, 160.0, 1000.0),
+        ]
+    }
+
+    fn assert_values(expected: &[f64], first_at: u64, estimator: VolatilityEstimator) {
+        let got = super::historical_volatility(&candlesticks(), 3, estimator, 252.0).unwrap();
+        assert_eq!(expected.len(), got.len());
+        assert_eq!(first_at, got[0].at);
+        for (x, y) in expected.iter().zip(&got) {
+            assert!(
+                (x - y.value).abs() < 1e-9,
+                "{:?}: {} != {}",
+                estimator,
+                x,
+                y
+            );
+        }
+    }
+
+    #[test]
+    fn test_close_to_close() {
+        assert_values(
+            &[
+                3.2089603637652004,
+                1.2221595629110256,
+                4.893050862318747,
+                4.0499082727826,
+                2.1718573745289245,
+                2.1215546630993023,
+                0.902056482334983,
+            ],
+            1719400004,
+            VolatilityEstimator::CloseToClose,
+        );
+    }
+
+    #[test]
+    fn test_parkinson() {
+        assert_values(
+            &[
+                2.819048979086583,
+                3.3153783642869166,
+                3.378755769813749,
+                3.9970626933970816,
+                3.427750838352527,
+                2.904383028659356,
+                1.9178041117158868,
+                1.0591282999302312,
+            ],
+            1719400003,
+            VolatilityEstimator::Parkinson,
+        );
+    }
+
+    #[test]
+    fn test_garman_klass() {
+        assert_values(
+            &[
+                3.0997943713180836,
+                3.5147428953170445,
+                3.683193756250158,
+                4.047751121231779,
+                3.3041856417441746,
+                2.5417093149251326,
+                1.8819910607687107,
+                1.1138725042006972,
+            ],
+            1719400003,
+            VolatilityEstimator::GarmanKlass,
+        );
+    }
+
+    #[test]
+    fn test_rogers_satchell() {
+        assert_values(
+            &[
+                3.0712827995989698,
+                3.455012514011128,
+                3.6649528068703496,
+                3.895186831583702,
+                3.0859263104090515,
+                2.2336004611315032,
+                1.77441926927656,
+                1.0818516842928783,
+            ],
+            1719400003,
+            VolatilityEstimator::RogersSatchell,
+        );
+    }
+
+    #[test]
+    fn test_yang_zhang() {
+        assert_values(
+            &[
+                3.4307721353568073,
+                3.5349831429471,
+                4.018868551900151,
+                3.1975549330309474,
+                2.238243671672897,
+                1.8036052578199628,
+                1.0649378275282104,
+            ],
+            1719400004,
+            VolatilityEstimator::YangZhang,
+        );
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        let xs = candlesticks();
+        let cc = VolatilityEstimator::CloseToClose;
+        assert!(super::historical_volatility(&xs, 1, cc, 252.0)
+            .unwrap()
+            .is_empty());
+        for periods_per_year in [0.0, f32::NAN, f32::INFINITY] {
+            assert!(matches!(
+                super::historical_volatility(&xs, 3, cc, periods_per_year),
+                Err(Error::MustBePositiveF32 { .. })
+            ));
+        }
+
+        let mut xs = xs;
+        xs[4].low = 0.0;
+        assert!(matches!(
+            super::historical_volatility(&xs, 3, cc, 252.0),
+            Err(Error::MustBePositive { at: 1719400005, .. })
+        ));
+    }
+}
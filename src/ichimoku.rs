@@ -13,9 +13,16 @@
 //!
 //! // Get Ichimoku calculation result with default common parameters
 //! let result = m4rs::ichimoku_default(&candlesticks);
+//!
+//! // Get Ichimoku Kinkohyo without displacement to avoid lookahead
+//! let result = m4rs::ichimoku_causal(&candlesticks, 9, 26, 52);
+//!
+//! // Get Ichimoku Kinkohyo displaced by 25 bars, skipping weekends beyond the last candlestick
+//! let calendar = m4rs::SessionCalendar::weekdays(0);
+//! let result = m4rs::ichimoku_with_calendar(&candlesticks, 9, 26, 52, 25, &calendar);
 //! ```
 
-use crate::{Candlestick, Error, IndexEntry, IndexEntryLike};
+use crate::{Candlestick, Error, IndexEntry, IndexEntryLike, TradingCalendar};
 
 #[derive(Debug)]
 pub struct IchimokuEntry {
@@ -88,6 +95,9 @@ pub fn ichimoku_default(entries: &[Candlestick]) -> Result<IchimokuData, Error>
 }
 
 /// Returns Ichimoku Kinkohyo for given Candlestick list with custom parameters
+///
+/// Spans are displaced by `lagging_span - 1` times the interval of the last two Candlesticks,
+/// which assumes no gap. Use `ichimoku_with_calendar` for series with gaps such as weekends.
 pub fn ichimoku(
     entries: &[Candlestick],
     conversion_line_len: usize,
@@ -108,6 +118,86 @@ pub fn ichimoku(
     })
 }
 
+/// Returns Ichimoku Kinkohyo at the time each value is known, for given Candlestick list
+///
+/// Leading spans are not shifted forward and lagging span is not shifted backward,
+/// so every line is keyed by the last bar used to calculate it. Use it to avoid lookahead
+/// in backtests. The cloud drawn at a bar by `ichimoku` with `lagging_span` is the leading
+/// spans `lagging_span - 1` bars before it, and lagging span is close of the bar.
+pub fn ichimoku_causal(
+    entries: &[Candlestick],
+    conversion_line_len: usize,
+    base_line_len: usize,
+    leading_span_b_len: usize,
+) -> Result<IchimokuData, Error> {
+    ichimoku(
+        entries,
+        conversion_line_len,
+        base_line_len,
+        leading_span_b_len,
+        0,
+    )
+}
+
+/// Returns Ichimoku Kinkohyo displaced by `displacement` bars for given Candlestick list
+///
+/// Leading spans are moved forward and lagging span backward by the number of Candlesticks,
+/// so gaps in the series are kept. Timestamps beyond the last Candlestick are generated by
+/// `calendar` with the shortest interval of the series, and lagging span before the first one
+/// is dropped. `displacement` of 25 places the spans as `ichimoku` with `lagging_span` of 26.
+pub fn ichimoku_with_calendar(
+    entries: &[Candlestick],
+    conversion_line_len: usize,
+    base_line_len: usize,
+    leading_span_b_len: usize,
+    displacement: usize,
+    calendar: &impl TradingCalendar,
+) -> Result<IchimokuData, Error> {
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.at);
+
+    let data = ichimoku_causal(
+        &sorted,
+        conversion_line_len,
+        base_line_len,
+        leading_span_b_len,
+    )?;
+
+    let ats: Vec<u64> = sorted.iter().map(|x| x.at).collect();
+    let mut timeline = ats.clone();
+    if let Some(interval) = ats.windows(2).map(|x| x[1] - x[0]).filter(|x| *x > 0).min() {
+        for _ in 0..displacement {
+            match calendar.next_bar(*timeline.last().unwrap(), interval) {
+                Some(at) => timeline.push(at),
+                None => break,
+            }
+        }
+    }
+    let shift = |xs: &[IndexEntry], forward: bool| -> Vec<IndexEntry> {
+        xs.iter()
+            .filter_map(|x| {
+                let i = ats.binary_search(&x.at).ok()?;
+                let j = if forward {
+                    i + displacement
+                } else {
+                    i.checked_sub(displacement)?
+                };
+                timeline.get(j).map(|at| IndexEntry {
+                    at: *at,
+                    value: x.value,
+                })
+            })
+            .collect()
+    };
+    Ok(IchimokuData {
+        leading_span_a: shift(&data.leading_span_a, true),
+        leading_span_b: shift(&data.leading_span_b, true),
+        lagging_span: shift(&data.lagging_span, false),
+        conversion_line: data.conversion_line,
+        base_line: data.base_line,
+    })
+}
+
 fn calc_base_and_conversion_line(entries: &[Candlestick], line_len: usize) -> Vec<IndexEntry> {
     if line_len == 0 {
         return vec![];
@@ -192,3 +282,81 @@ fn apply_lag(entries: &[impl IndexEntryLike], span: usize, backward: bool) -> Ve
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::Candlestick;
+
+    fn candlesticks() -> Vec<Candlestick> {
+        vec![
+            Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+            Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+            Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+            Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+        ]
+    }
+
+    #[test]
+    fn test_ichimoku() {
+        let got = super::ichimoku(&candlesticks(), 2, 3, 4, 2).unwrap();
+        assert_eq!(1719400003, got.base_line[0].at);
+        assert_eq!(115.0, got.base_line[0].value);
+        // shifted 1 bar forward and backward
+        assert_eq!(1719400004, got.leading_span_a[0].at);
+        assert_eq!(117.5, got.leading_span_a[0].value);
+        assert_eq!(1719400005, got.leading_span_b[0].at);
+        assert_eq!(1719400000, got.lagging_span[0].at);
+        assert_eq!(110.0, got.lagging_span[0].value);
+    }
+
+    #[test]
+    fn test_ichimoku_causal() {
+        let got = super::ichimoku_causal(&candlesticks(), 2, 3, 4).unwrap();
+        assert_eq!(115.0, got.base_line[0].value);
+        assert_eq!(1719400003, got.leading_span_a[0].at);
+        assert_eq!(117.5, got.leading_span_a[0].value);
+        assert_eq!(1719400004, got.leading_span_b[0].at);
+        assert_eq!(110.0, got.leading_span_b[0].value);
+        assert_eq!(1719400001, got.lagging_span[0].at);
+        assert_eq!(110.0, got.lagging_span[0].value);
+
+        // every line is known at the last candlestick at the latest
+        let entry = got.get(1719400005).unwrap();
+        assert_eq!(Some(82.0), entry.lagging_span);
+        assert_eq!(Some(105.0), entry.leading_span_b);
+    }
+
+    #[test]
+    fn test_ichimoku_with_calendar() {
+        // daily candlesticks from Wednesday 2024-06-26 to Monday 2024-07-01 in UTC, skipping weekend
+        let xs: Vec<Candlestick> = candlesticks()
+            .iter()
+            .zip([1719360000, 1719446400, 1719532800, 1719792000, 1719878400])
+            .map(|(x, at)| Candlestick { at, ..x.clone() })
+            .collect();
+        let calendar = crate::SessionCalendar::weekdays(0);
+        let got = super::ichimoku_with_calendar(&xs, 2, 3, 4, 2, &calendar).unwrap();
+
+        // Friday is followed by Monday within the series
+        assert_eq!(1719878400, got.leading_span_a[0].at);
+        assert_eq!(117.5, got.leading_span_a[0].value);
+        // Wednesday and Thursday are projected
+        let ats: Vec<u64> = got.leading_span_b.iter().map(|x| x.at).collect();
+        assert_eq!(vec![1719964800, 1720051200], ats);
+        assert_eq!(105.0, got.leading_span_b[1].value);
+        // lagging span before the first candlestick is dropped
+        assert_eq!(3, got.lagging_span.len());
+        assert_eq!(
+            (1719360000, 120.0),
+            (got.lagging_span[0].at, got.lagging_span[0].value)
+        );
+
+        // projected over weekend from Friday
+        let got = super::ichimoku_with_calendar(&xs[..3], 2, 3, 4, 2, &calendar).unwrap();
+        assert_eq!(1719878400, got.leading_span_a[0].at);
+        // crypto market does not skip weekend
+        let got = super::ichimoku_with_calendar(&xs[..3], 2, 3, 4, 2, &crate::Continuous).unwrap();
+        assert_eq!(1719705600, got.leading_span_a[0].at);
+    }
+}
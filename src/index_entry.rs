@@ -10,8 +10,26 @@ pub trait IndexEntryLike: Clone {
     fn get_value(&self) -> f64;
 }
 
+/// Abstract type of entries having high, low and close, such as Candlestick
+///
+/// Range-based indicators such as stochastics accept it, so they also work over
+/// IndexEntry list of any indicator, where high, low and close are all the value.
+pub trait HighLowCloseLike: IndexEntryLike {
+    fn get_high(&self) -> f64;
+    fn get_low(&self) -> f64;
+    fn get_close(&self) -> f64;
+
+    /// Returns error if any field is NaN or infinite, checking high, low and close by default
+    fn validate(&self) -> Result<(), Error> {
+        IndexEntry::validate_field(self.get_at(), self.get_high(), "high")?;
+        IndexEntry::validate_field(self.get_at(), self.get_low(), "low")?;
+        IndexEntry::validate_field(self.get_at(), self.get_close(), "close")
+    }
+}
+
 /// Simple index entry
 #[derive(Debug, Clone)]
+#[repr(C)]
 pub struct IndexEntry {
     pub at: u64,
     pub value: f64,
@@ -33,6 +51,20 @@ impl IndexEntryLike for IndexEntry {
     }
 }
 
+impl HighLowCloseLike for IndexEntry {
+    fn get_high(&self) -> f64 {
+        self.value
+    }
+
+    fn get_low(&self) -> f64 {
+        self.value
+    }
+
+    fn get_close(&self) -> f64 {
+        self.value
+    }
+}
+
 impl IndexEntry {
     pub fn new(at: u64, value: f64) -> IndexEntry {
         IndexEntry { at, value }
@@ -68,12 +100,32 @@ impl IndexEntry {
         }
         Ok(())
     }
+
+    pub(crate) fn validate_hlc_list<T: HighLowCloseLike>(xs: &[T]) -> Result<(), Error> {
+        for x in xs {
+            x.validate()?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns pairs of entries at the same timestamps from the ends of two lists
+///
+/// Both lists must be calculated over the same sorted list, where each one is its suffix
+/// such as outputs of moving averages, so that they are joined by index without search.
+pub(crate) fn zip_tails<'a, A: IndexEntryLike, B: IndexEntryLike>(
+    xs: &'a [A],
+    ys: &'a [B],
+) -> impl Iterator<Item = (&'a A, &'a B)> {
+    let n = xs.len().min(ys.len());
+    xs[xs.len() - n..]
+        .iter()
+        .zip(&ys[ys.len() - n..])
+        .filter(|(x, y)| x.get_at() == y.get_at())
 }
 
 #[cfg(test)]
 mod tests {
-    use std::f64::{INFINITY, NAN, NEG_INFINITY};
-
     use super::*;
     use crate::Error;
 
@@ -82,17 +134,17 @@ mod tests {
         let res = IndexEntry::validate_field(1719400001, 100.0, "field1");
         assert!(res.is_ok());
 
-        let res = IndexEntry::validate_field(1719400001, NAN, "field1");
+        let res = IndexEntry::validate_field(1719400001, f64::NAN, "field1");
         assert!(
             matches!(res, Err(Error::ContainsNaN { at: 1719400001, field }) if field == "field1")
         );
 
-        let res = IndexEntry::validate_field(1719400002, INFINITY, "field2");
+        let res = IndexEntry::validate_field(1719400002, f64::INFINITY, "field2");
         assert!(
             matches!(res, Err(Error::ContainsInfinite { at: 1719400002, field }) if field == "field2")
         );
 
-        let res = IndexEntry::validate_field(1719400003, NEG_INFINITY, "field3");
+        let res = IndexEntry::validate_field(1719400003, f64::NEG_INFINITY, "field3");
         assert!(
             matches!(res, Err(Error::ContainsInfinite { at: 1719400003, field }) if field == "field3")
         );
@@ -101,7 +153,7 @@ mod tests {
     #[test]
     fn test_validate_list() {
         // valid list
-        let res = IndexEntry::validate_list(&vec![
+        let res = IndexEntry::validate_list(&[
             IndexEntry::new(1719400001, 100.0),
             IndexEntry::new(1719400002, 110.0),
             IndexEntry::new(1719400003, 130.0),
@@ -111,10 +163,10 @@ mod tests {
         assert!(res.is_ok());
 
         // invalid: contains NAN
-        let res = IndexEntry::validate_list(&vec![
+        let res = IndexEntry::validate_list(&[
             IndexEntry::new(1719400001, 100.0),
             IndexEntry::new(1719400002, 110.0),
-            IndexEntry::new(1719400003, NAN),
+            IndexEntry::new(1719400003, f64::NAN),
             IndexEntry::new(1719400004, 120.0),
             IndexEntry::new(1719400005, 90.0),
         ]);
@@ -123,11 +175,11 @@ mod tests {
         );
 
         // invalid: contains INFINITY
-        let res = IndexEntry::validate_list(&vec![
+        let res = IndexEntry::validate_list(&[
             IndexEntry::new(1719400001, 100.0),
             IndexEntry::new(1719400002, 110.0),
             IndexEntry::new(1719400003, 130.0),
-            IndexEntry::new(1719400004, INFINITY),
+            IndexEntry::new(1719400004, f64::INFINITY),
             IndexEntry::new(1719400005, 90.0),
         ]);
         assert!(
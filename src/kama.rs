@@ -0,0 +1,97 @@
+//! KAMA (Kaufman Adaptive Moving Average)
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get KAMA of 10 bars efficiency ratio with 2 and 30 bars smoothing
+//! let result = m4rs::kama(&candlesticks, 10, 2, 30);
+//! ```
+
+use crate::{Error, IndexEntry, IndexEntryLike};
+
+/// Returns KAMA (Kaufman Adaptive Moving Average) for given IndexEntry list
+///
+/// Smoothing moves between EMA of `fast_duration` and `slow_duration` according to
+/// the efficiency ratio of price change to volatility over `duration`.
+pub fn kama(
+    entries: &[impl IndexEntryLike],
+    duration: usize,
+    fast_duration: usize,
+    slow_duration: usize,
+) -> Result<Vec<IndexEntry>, Error> {
+    if slow_duration < fast_duration {
+        return Err(Error::LongDurationIsNotGreaterThanShortDuration {
+            short_duration: fast_duration,
+            long_duration: slow_duration,
+        });
+    }
+    if duration == 0 || fast_duration == 0 || entries.len() <= duration {
+        return Ok(vec![]);
+    }
+    IndexEntry::validate_list(entries)?;
+
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.get_at());
+    let values: Vec<f64> = sorted.iter().map(|x| x.get_value()).collect();
+
+    let fast = 2.0 / (fast_duration as f64 + 1.0);
+    let slow = 2.0 / (slow_duration as f64 + 1.0);
+    let mut z = values[duration - 1];
+    Ok((duration..sorted.len())
+        .map(|i| {
+            let change = (values[i] - values[i - duration]).abs();
+            let volatility = values[i - duration..=i]
+                .windows(2)
+                .fold(0.0, |z, x| z + (x[1] - x[0]).abs());
+            let er = if volatility == 0.0 {
+                0.0
+            } else {
+                change / volatility
+            };
+            let sc = (er * (fast - slow) + slow).powi(2);
+            z += sc * (values[i] - z);
+            IndexEntry {
+                at: sorted[i].get_at(),
+                value: z,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, IndexEntry};
+
+    #[test]
+    fn test_kama() {
+        let xs = vec![
+            IndexEntry::new(1719400001, 110.0),
+            IndexEntry::new(1719400002, 130.0),
+            IndexEntry::new(1719400003, 120.0),
+            IndexEntry::new(1719400004, 95.0),
+            IndexEntry::new(1719400005, 82.0),
+            IndexEntry::new(1719400006, 115.0),
+            IndexEntry::new(1719400007, 145.0),
+            IndexEntry::new(1719400008, 155.0),
+        ];
+        let got = super::kama(&xs, 3, 2, 30).unwrap();
+        assert_eq!(5, got.len());
+        assert_eq!(1719400004, got[0].at);
+        assert!((got[0].value - 118.69196171343556).abs() < 1e-9);
+        assert!((got[1].value - 102.38442317413087).abs() < 1e-9);
+        assert!((got[4].value - 130.85649819373936).abs() < 1e-9);
+
+        assert!(matches!(
+            super::kama(&xs, 3, 30, 2),
+            Err(Error::LongDurationIsNotGreaterThanShortDuration { .. })
+        ));
+    }
+}
@@ -0,0 +1,101 @@
+//! Keltner Channel
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get Keltner Channel of 20EMA with 2x 10ATR
+//! let result = m4rs::keltner_channel(&candlesticks, 20, 10, 2.0, m4rs::MaType::Ema);
+//! ```
+
+use std::fmt::Display;
+
+use crate::{atr, index_entry::zip_tails, Candlestick, Error, IndexEntryLike, MaType};
+
+#[derive(Clone, Debug)]
+pub struct KeltnerChannelEntry {
+    pub at: u64,
+    pub basis: f64,
+    pub upper: f64,
+    pub lower: f64,
+}
+
+impl Display for KeltnerChannelEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "KeltnerChannel(at={} basis={} upper={} lower={})",
+            self.at, self.basis, self.upper, self.lower,
+        )
+    }
+}
+
+impl IndexEntryLike for KeltnerChannelEntry {
+    fn get_at(&self) -> u64 {
+        self.at
+    }
+
+    fn get_value(&self) -> f64 {
+        self.basis
+    }
+}
+
+/// Returns Keltner Channel, moving average of close by `ma` with bands of ATR multiplied by `multiplier`
+pub fn keltner_channel(
+    entries: &[Candlestick],
+    duration: usize,
+    atr_duration: usize,
+    multiplier: f32,
+    ma: MaType,
+) -> Result<Vec<KeltnerChannelEntry>, Error> {
+    let mas = ma.calc(entries, duration)?;
+    let atrs = atr(entries, atr_duration)?;
+    Ok(zip_tails(&mas, &atrs)
+        .map(|(x, a)| {
+            let width = a.value * multiplier as f64;
+            KeltnerChannelEntry {
+                at: a.at,
+                basis: x.value,
+                upper: x.value + width,
+                lower: x.value - width,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Candlestick, MaType};
+
+    #[test]
+    fn test_keltner_channel() {
+        let xs = vec![
+            Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+            Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+            Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+            Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+        ];
+        let got = super::keltner_channel(&xs, 2, 2, 2.0, MaType::Ema).unwrap();
+        assert_eq!(2, got.len());
+        assert_eq!(1719400004, got[0].at);
+        assert!((got[0].basis - 103.33333333333334).abs() < 1e-9);
+        assert!((got[0].upper - 180.83333333333334).abs() < 1e-9);
+        assert!((got[0].lower - 25.833333333333343).abs() < 1e-9);
+        assert!((got[1].basis - 89.11111111111111).abs() < 1e-9);
+        assert!((got[1].upper - 157.86111111111111).abs() < 1e-9);
+
+        let got = super::keltner_channel(&xs, 2, 2, 1.0, MaType::Sma).unwrap();
+        assert_eq!(107.5, got[0].basis);
+        assert_eq!(146.25, got[0].upper);
+        assert_eq!(88.5, got[1].basis);
+        assert_eq!(54.125, got[1].lower);
+    }
+}
@@ -0,0 +1,116 @@
+//! KST (Know Sure Thing)
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get KST with its default parameters
+//! let result = m4rs::kst(&candlesticks, [10, 15, 20, 30], [10, 10, 10, 15], 9);
+//! ```
+
+use std::fmt::Display;
+
+use crate::{index_entry::zip_tails, roc, sma, Error, IndexEntry, IndexEntryLike};
+
+#[derive(Clone, Debug)]
+pub struct KstEntry {
+    pub at: u64,
+    pub kst: f64,
+    pub signal: f64,
+}
+
+impl Display for KstEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "KST(at={} kst={} sig={})",
+            self.at, self.kst, self.signal
+        )
+    }
+}
+
+impl IndexEntryLike for KstEntry {
+    fn get_at(&self) -> u64 {
+        self.at
+    }
+
+    fn get_value(&self) -> f64 {
+        self.kst
+    }
+}
+
+/// Returns KST (Know Sure Thing) for given IndexEntry list
+///
+/// Sum of four ROCs of `roc_durations` smoothed by SMAs of `sma_durations`,
+/// weighted 1 to 4, with SMA of `signal_duration` as the signal.
+pub fn kst(
+    entries: &[impl IndexEntryLike],
+    roc_durations: [usize; 4],
+    sma_durations: [usize; 4],
+    signal_duration: usize,
+) -> Result<Vec<KstEntry>, Error> {
+    let parts = roc_durations
+        .iter()
+        .zip(sma_durations)
+        .map(|(r, s)| sma(&roc(entries, *r)?, s))
+        .collect::<Result<Vec<Vec<IndexEntry>>, Error>>()?;
+    let ksts = parts
+        .iter()
+        .enumerate()
+        .skip(1)
+        .fold(parts[0].clone(), |z, (i, xs)| {
+            zip_tails(&z, xs)
+                .map(|(z, x)| IndexEntry {
+                    at: x.at,
+                    value: z.value + x.value * (i + 1) as f64,
+                })
+                .collect()
+        });
+    let signals = sma(&ksts, signal_duration)?;
+    Ok(zip_tails(&ksts, &signals)
+        .map(|(x, s)| KstEntry {
+            at: x.at,
+            kst: x.value,
+            signal: s.value,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IndexEntry;
+
+    #[test]
+    fn test_kst() {
+        let xs = vec![
+            IndexEntry::new(1719400001, 110.0),
+            IndexEntry::new(1719400002, 130.0),
+            IndexEntry::new(1719400003, 120.0),
+            IndexEntry::new(1719400004, 95.0),
+            IndexEntry::new(1719400005, 82.0),
+            IndexEntry::new(1719400006, 115.0),
+            IndexEntry::new(1719400007, 145.0),
+            IndexEntry::new(1719400008, 155.0),
+            IndexEntry::new(1719400009, 150.0),
+            IndexEntry::new(1719400010, 160.0),
+            IndexEntry::new(1719400011, 140.0),
+            IndexEntry::new(1719400012, 135.0),
+            IndexEntry::new(1719400013, 150.0),
+            IndexEntry::new(1719400014, 165.0),
+        ];
+        let got = super::kst(&xs, [1, 2, 3, 4], [1, 1, 1, 2], 2).unwrap();
+        assert_eq!(8, got.len());
+        assert_eq!(1719400007, got[0].at);
+        assert!((got[0].kst - 356.22997353895386).abs() < 1e-9);
+        assert!((got[0].signal - 176.0465625749295).abs() < 1e-9);
+        assert!((got[7].kst - 114.26587301587301).abs() < 1e-9);
+        assert!((got[7].signal - 47.55312339989759).abs() < 1e-9);
+    }
+}
@@ -1,57 +1,141 @@
+pub mod accumulation_distribution;
+pub mod alma;
+pub mod aroon;
 pub mod atr;
 pub mod awesome_oscillator;
 pub mod bolinger_band;
+pub mod calendar;
 pub mod candlestick;
 pub mod cci;
+pub mod chaikin_money_flow;
+pub mod chaikin_oscillator;
+pub mod chart;
+pub mod choppiness_index;
+pub mod cmo;
+pub mod connors_rsi;
+pub mod coppock;
+pub mod corporate_action;
 pub mod dema;
 pub mod dmi;
+pub mod donchian_channel;
 pub mod ema;
 pub mod envelope;
 pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod fibonacci;
+pub mod force_index;
+pub mod gaps;
 pub mod heikin_ashi;
+pub mod historical_volatility;
 pub mod hma;
 pub mod ichimoku;
 pub mod index_entry;
+pub mod kama;
+pub mod keltner_channel;
+pub mod kst;
+pub mod ma_type;
 pub mod macd;
+pub mod mass_index;
+pub mod mcginley_dynamic;
+pub mod mfi;
 pub mod momentum;
+pub mod obv;
 pub mod parabolic_sar;
+pub mod pivot_points;
+pub mod ppo;
 pub mod rci;
+pub mod registry;
 pub mod rma;
+pub mod roc;
 pub mod rsi;
 pub mod sma;
 pub mod standard_deviation;
+pub mod stoch_rsi;
 pub mod stochastics;
+pub mod supertrend;
+pub mod svg;
+pub mod t3;
 pub mod tema;
+pub mod terminal;
+pub mod trix;
+pub mod ultimate_oscillator;
+pub mod vidya;
+pub mod vortex;
+pub mod vwap;
 pub mod vwma;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 pub mod williams_fractals;
 pub mod williams_percent_r;
 pub mod wma;
+pub mod zigzag;
+pub mod zlema;
 
+pub use accumulation_distribution::*;
+pub use alma::*;
+pub use aroon::*;
 pub use atr::*;
 pub use awesome_oscillator::*;
 pub use bolinger_band::*;
+pub use calendar::*;
 pub use candlestick::*;
 pub use cci::*;
+pub use chaikin_money_flow::*;
+pub use chaikin_oscillator::*;
+pub use chart::*;
+pub use choppiness_index::*;
+pub use cmo::*;
+pub use connors_rsi::*;
+pub use coppock::*;
+pub use corporate_action::*;
 pub use dema::*;
 pub use dmi::*;
+pub use donchian_channel::*;
 pub use ema::*;
 pub use envelope::*;
 pub use error::*;
+pub use fibonacci::*;
+pub use force_index::*;
+pub use gaps::*;
 pub use heikin_ashi::*;
+pub use historical_volatility::*;
 pub use hma::*;
 pub use ichimoku::*;
 pub use index_entry::*;
+pub use kama::*;
+pub use keltner_channel::*;
+pub use kst::*;
+pub use ma_type::*;
 pub use macd::*;
+pub use mass_index::*;
+pub use mcginley_dynamic::*;
+pub use mfi::*;
 pub use momentum::*;
+pub use obv::*;
 pub use parabolic_sar::*;
+pub use pivot_points::*;
+pub use ppo::*;
 pub use rci::*;
+pub use registry::*;
 pub use rma::*;
+pub use roc::*;
 pub use rsi::*;
 pub use sma::*;
 pub use standard_deviation::*;
+pub use stoch_rsi::*;
 pub use stochastics::*;
+pub use supertrend::*;
+pub use t3::*;
 pub use tema::*;
+pub use trix::*;
+pub use ultimate_oscillator::*;
+pub use vidya::*;
+pub use vortex::*;
+pub use vwap::*;
 pub use vwma::*;
 pub use williams_fractals::*;
 pub use williams_percent_r::*;
 pub use wma::*;
+pub use zigzag::*;
+pub use zlema::*;
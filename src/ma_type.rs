@@ -0,0 +1,145 @@
+//! Selector of moving average used by composite indicators
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get 3WMA calculation result
+//! let result = m4rs::MaType::Wma.calc(&candlesticks, 3);
+//!
+//! // Get Bolinger Band around 3EMA
+//! let result = m4rs::bolinger_band_with_ma(&candlesticks, 3, m4rs::MaType::Ema);
+//!
+//! // Get MACD with SMA signal
+//! let result = m4rs::macd_with_ma(&candlesticks, 2, 3, 2, m4rs::MaType::Ema, m4rs::MaType::Sma);
+//! ```
+
+use crate::{
+    alma, dema, ema, hma, kama, mcginley_dynamic, rma, sma, t3, tema, vidya, wma, zlema, Error,
+    IndexEntry, IndexEntryLike,
+};
+
+/// Moving average applicable to any IndexEntry list
+///
+/// VWMA is not included since it needs volume of Candlestick.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MaType {
+    Sma,
+    Ema,
+    Wma,
+    Rma,
+    Dema,
+    Tema,
+    Hma,
+    Zlema,
+    Kama {
+        fast_duration: usize,
+        slow_duration: usize,
+    },
+    Alma {
+        offset: f32,
+        sigma: f32,
+    },
+    T3 {
+        volume_factor: f32,
+    },
+    McGinleyDynamic,
+    Vidya,
+}
+
+impl MaType {
+    /// Returns the moving average of `duration` for given IndexEntry list
+    pub fn calc(
+        &self,
+        entries: &[impl IndexEntryLike],
+        duration: usize,
+    ) -> Result<Vec<IndexEntry>, Error> {
+        match *self {
+            MaType::Sma => sma(entries, duration),
+            MaType::Ema => ema(entries, duration),
+            MaType::Wma => wma(entries, duration),
+            MaType::Rma => rma(entries, duration),
+            MaType::Dema => dema(entries, duration),
+            MaType::Tema => tema(entries, duration),
+            MaType::Hma => hma(entries, duration),
+            MaType::Zlema => zlema(entries, duration),
+            MaType::Kama {
+                fast_duration,
+                slow_duration,
+            } => kama(entries, duration, fast_duration, slow_duration),
+            MaType::Alma { offset, sigma } => alma(entries, duration, offset, sigma),
+            MaType::T3 { volume_factor } => t3(entries, duration, volume_factor),
+            MaType::McGinleyDynamic => mcginley_dynamic(entries, duration),
+            MaType::Vidya => vidya(entries, duration),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Candlestick, IndexEntry, MaType};
+
+    #[test]
+    fn test_calc() {
+        let xs = vec![
+            IndexEntry::new(1719400001, 110.0),
+            IndexEntry::new(1719400002, 130.0),
+            IndexEntry::new(1719400003, 120.0),
+            IndexEntry::new(1719400004, 95.0),
+            IndexEntry::new(1719400005, 82.0),
+        ];
+        let got = MaType::Sma.calc(&xs, 2).unwrap();
+        assert_eq!(4, got.len());
+        assert_eq!(120.0, got[0].value);
+
+        let got = MaType::Ema.calc(&xs, 2).unwrap();
+        assert_eq!(3, got.len());
+        assert_eq!(1719400003, got[0].at);
+
+        let alma = MaType::Alma {
+            offset: 0.85,
+            sigma: 6.0,
+        };
+        assert_eq!(
+            crate::alma(&xs, 3, 0.85, 6.0).unwrap()[0].value,
+            alma.calc(&xs, 3).unwrap()[0].value
+        );
+    }
+
+    #[test]
+    fn test_composites() {
+        let xs = vec![
+            Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+            Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+            Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+            Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+        ];
+        let got = crate::bolinger_band_with_ma(&xs, 3, MaType::Ema).unwrap();
+        assert_eq!(2, got.len());
+        assert_eq!(1719400004, got[0].at);
+        assert_eq!(107.5, got[0].avg);
+        assert!((got[0].sigma - 14.719601443879744).abs() < 1e-9);
+        assert_eq!(94.75, got[1].avg);
+
+        let got = crate::macd_with_ma(&xs, 2, 3, 2, MaType::Ema, MaType::Sma).unwrap();
+        assert_eq!(1, got.len());
+        assert_eq!(1719400005, got[0].at);
+        assert!((got[0].macd - -5.638888888888886).abs() < 1e-9);
+        assert!((got[0].signal - -4.9027777777777715).abs() < 1e-9);
+        assert!((got[0].histogram - -0.7361111111111143).abs() < 1e-9);
+
+        // defaults are kept
+        let got = crate::stochastics_with_ma(&xs, 3, 2, MaType::Sma).unwrap();
+        let want = crate::stochastics(&xs, 3, 2).unwrap();
+        assert_eq!(want.len(), got.len());
+        assert_eq!(want[0].d, got[0].d);
+    }
+}
@@ -17,9 +17,10 @@
 
 use std::fmt::Display;
 
-use crate::{ema, Error, IndexEntryLike};
+use crate::{Error, IndexEntryLike, MaType};
 
 #[derive(Debug, Clone)]
+#[repr(C)]
 pub struct MacdEntry {
     pub at: u64,
     pub macd: f64,
@@ -53,6 +54,25 @@ pub fn macd(
     short_duration: usize,
     long_duration: usize,
     signal_duration: usize,
+) -> Result<Vec<MacdEntry>, Error> {
+    macd_with_ma(
+        entries,
+        short_duration,
+        long_duration,
+        signal_duration,
+        MaType::Ema,
+        MaType::Ema,
+    )
+}
+
+/// Returns MACD whose oscillator uses `ma` and signal uses `signal_ma` as moving average
+pub fn macd_with_ma(
+    entries: &[impl IndexEntryLike],
+    short_duration: usize,
+    long_duration: usize,
+    signal_duration: usize,
+    ma: MaType,
+    signal_ma: MaType,
 ) -> Result<Vec<MacdEntry>, Error> {
     if long_duration < short_duration {
         return Err(Error::LongDurationIsNotGreaterThanShortDuration {
@@ -63,12 +83,12 @@ pub fn macd(
     if entries.is_empty() || short_duration == 0 || long_duration == 0 || signal_duration == 0 {
         return Ok(vec![]);
     }
-    let ema_s = ema(entries, short_duration)?;
-    let ema_l = ema(entries, long_duration)?;
-    let macds: Vec<MacdEntry> = ema_l
+    let ma_s = ma.calc(entries, short_duration)?;
+    let ma_l = ma.calc(entries, long_duration)?;
+    let macds: Vec<MacdEntry> = ma_l
         .iter()
         .filter_map(|l| {
-            ema_s.iter().find(|s| s.at == l.at).map(|s| MacdEntry {
+            ma_s.iter().find(|s| s.at == l.at).map(|s| MacdEntry {
                 at: s.at,
                 macd: s.value - l.value,
                 signal: 0.0,
@@ -76,7 +96,7 @@ pub fn macd(
             })
         })
         .collect();
-    let signals = ema(&macds, signal_duration)?;
+    let signals = signal_ma.calc(&macds, signal_duration)?;
     Ok(macds
         .iter()
         .filter_map(|x| {
@@ -0,0 +1,103 @@
+//! Mass Index
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get Mass Index of 9EMA summed over 25 bars
+//! let result = m4rs::mass_index(&candlesticks, 9, 25);
+//! ```
+
+use crate::{ema, index_entry::zip_tails, Candlestick, Error, IndexEntry};
+
+/// Returns Mass Index for given Candlestick list
+///
+/// Sum over `sum_duration` of the ratio of EMA of high-low range to EMA of that EMA.
+/// Windows containing bars where the EMA of EMA is zero, such as leading flat bars, have no entry.
+pub fn mass_index(
+    entries: &[Candlestick],
+    ema_duration: usize,
+    sum_duration: usize,
+) -> Result<Vec<IndexEntry>, Error> {
+    if sum_duration == 0 || entries.len() < ema_duration {
+        return Ok(vec![]);
+    }
+    Candlestick::validate_list(entries)?;
+
+    let ranges: Vec<IndexEntry> = entries
+        .iter()
+        .map(|x| IndexEntry {
+            at: x.at,
+            value: x.high - x.low,
+        })
+        .collect();
+    let ema1 = ema(&ranges, ema_duration)?;
+    let ema2 = ema(&ema1, ema_duration)?;
+    let ratios: Vec<(u64, Option<f64>)> = zip_tails(&ema1, &ema2)
+        .map(|(e1, e2)| (e2.at, (e2.value != 0.0).then(|| e1.value / e2.value)))
+        .collect();
+    Ok(ratios
+        .windows(sum_duration)
+        .filter_map(|xs| {
+            Some(IndexEntry {
+                at: xs.last().unwrap().0,
+                value: xs.iter().map(|x| x.1).sum::<Option<f64>>()?,
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Candlestick;
+
+    #[test]
+    fn test_mass_index() {
+        let xs = vec![
+            Candlestick::new(1719400001, 110.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 130.0, 140.0, 100.0, 130.0, 1000.0),
+            Candlestick::new(1719400003, 120.0, 135.0, 120.0, 120.0, 1000.0),
+            Candlestick::new(1719400004, 95.0, 130.0, 80.0, 95.0, 1000.0),
+            Candlestick::new(1719400005, 82.0, 100.0, 70.0, 82.0, 1000.0),
+            Candlestick::new(1719400006, 115.0, 120.0, 80.0, 115.0, 1000.0),
+            Candlestick::new(1719400007, 145.0, 150.0, 110.0, 145.0, 1000.0),
+            Candlestick::new(1719400008, 155.0, 160.0, 140.0, 155.0, 1000.0),
+            Candlestick::new(1719400009, 150.0, 158.0, 145.0, 150.0, 1000.0),
+            Candlestick::new(1719400010, 160.0, 165.0, 148.0, 160.0, 1000.0),
+            Candlestick::new(1719400011, 140.0, 162.0, 135.0, 140.0, 1000.0),
+            Candlestick::new(1719400012, 135.0, 145.0, 128.0, 135.0, 1000.0),
+        ];
+        let got = super::mass_index(&xs, 2, 3).unwrap();
+        assert_eq!(6, got.len());
+        assert_eq!(1719400007, got[0].at);
+        assert!((got[0].value - 3.083683728609255).abs() < 1e-9);
+        assert!((got[5].value - 2.947099603207654).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_mass_index_flat() {
+        // flat bars filled in gaps
+        let xs = vec![
+            Candlestick::new(1719400001, 110.0, 110.0, 110.0, 110.0, 0.0),
+            Candlestick::new(1719400002, 110.0, 110.0, 110.0, 110.0, 0.0),
+            Candlestick::new(1719400003, 110.0, 110.0, 110.0, 110.0, 0.0),
+            Candlestick::new(1719400004, 110.0, 110.0, 110.0, 110.0, 0.0),
+            Candlestick::new(1719400005, 110.0, 110.0, 110.0, 110.0, 0.0),
+            Candlestick::new(1719400006, 110.0, 130.0, 90.0, 120.0, 1000.0),
+            Candlestick::new(1719400007, 120.0, 140.0, 100.0, 130.0, 1000.0),
+            Candlestick::new(1719400008, 130.0, 150.0, 110.0, 140.0, 1000.0),
+        ];
+        let got = super::mass_index(&xs, 2, 2).unwrap();
+        assert_eq!(2, got.len());
+        assert_eq!(1719400007, got[0].at);
+        assert!((got[0].value - 2.7).abs() < 1e-9);
+        assert!((got[1].value - 2.283333333333333).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,110 @@
+//! McGinley Dynamic
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get 14 bars McGinley Dynamic calculation result
+//! let result = m4rs::mcginley_dynamic(&candlesticks, 14);
+//! ```
+
+use crate::{Error, IndexEntry, IndexEntryLike};
+
+/// Returns McGinley Dynamic for given IndexEntry list
+///
+/// Starts from SMA of the first `duration` entries and follows the value faster
+/// when it falls than when it rises. Zero values, which it divides by, are errors.
+pub fn mcginley_dynamic(
+    entries: &[impl IndexEntryLike],
+    duration: usize,
+) -> Result<Vec<IndexEntry>, Error> {
+    if duration == 0 || entries.len() < duration {
+        return Ok(vec![]);
+    }
+    IndexEntry::validate_list(entries)?;
+
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.get_at());
+
+    let n = duration as f64;
+    let mut z = sorted
+        .iter()
+        .take(duration)
+        .fold(0.0, |z, x| z + x.get_value())
+        / n;
+    sorted
+        .iter()
+        .skip(duration)
+        .map(|x| {
+            if z == 0.0 {
+                return Err(Error::DividedByZero {
+                    at: x.get_at(),
+                    field: "previous value".to_string(),
+                });
+            }
+            let v = x.get_value();
+            if v == 0.0 {
+                return Err(Error::DividedByZero {
+                    at: x.get_at(),
+                    field: "value".to_string(),
+                });
+            }
+            z += (v - z) / (n * (v / z).powi(4));
+            Ok(IndexEntry {
+                at: x.get_at(),
+                value: z,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, IndexEntry};
+
+    #[test]
+    fn test_mcginley_dynamic() {
+        let xs = vec![
+            IndexEntry::new(1719400001, 110.0),
+            IndexEntry::new(1719400002, 130.0),
+            IndexEntry::new(1719400003, 120.0),
+            IndexEntry::new(1719400004, 95.0),
+            IndexEntry::new(1719400005, 82.0),
+            IndexEntry::new(1719400006, 115.0),
+            IndexEntry::new(1719400007, 145.0),
+            IndexEntry::new(1719400008, 155.0),
+        ];
+        let got = super::mcginley_dynamic(&xs, 3).unwrap();
+        assert_eq!(5, got.len());
+        assert_eq!(1719400004, got[0].at);
+        assert!((got[0].value - 98.78469318068461).abs() < 1e-9);
+        assert!((got[1].value - 87.00062181979953).abs() < 1e-9);
+        assert!((got[4].value - 95.44581033602392).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_mcginley_dynamic_zero() {
+        let xs = vec![
+            IndexEntry::new(1719400001, 110.0),
+            IndexEntry::new(1719400002, 130.0),
+            IndexEntry::new(1719400003, 0.0),
+            IndexEntry::new(1719400004, 95.0),
+        ];
+        let got = super::mcginley_dynamic(&xs, 2);
+        assert!(
+            matches!(got, Err(Error::DividedByZero { at: 1719400003, field }) if field == "value")
+        );
+
+        let got = super::mcginley_dynamic(&xs[2..], 1);
+        assert!(
+            matches!(got, Err(Error::DividedByZero { at: 1719400004, field }) if field == "previous value")
+        );
+    }
+}
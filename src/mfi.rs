@@ -0,0 +1,105 @@
+//! MFI (Money Flow Index)
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get MFI calculation result
+//! let result = m4rs::mfi(&candlesticks, 14);
+//! ```
+
+use crate::{Candlestick, Error, IndexEntry};
+
+/// Returns MFI (Money Flow Index) for given Candlestick list
+///
+/// Money flow (typical price x volume) of a bar is positive if its typical price
+/// is higher than the previous one, and negative if lower.
+/// MFI is 50 for a window without money flow, such as flat or zero-volume bars.
+pub fn mfi(entries: &[Candlestick], duration: usize) -> Result<Vec<IndexEntry>, Error> {
+    if duration == 0 || entries.len() <= duration {
+        return Ok(vec![]);
+    }
+    Candlestick::validate_list(entries)?;
+
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.at);
+
+    // signed money flow of each bar after the first
+    let flows: Vec<(u64, f64)> = sorted
+        .windows(2)
+        .map(|xs| {
+            let (prev, x) = (xs[0].typical_price(), xs[1].typical_price());
+            let flow = x * xs[1].volume;
+            let sign = if x > prev {
+                1.0
+            } else if x < prev {
+                -1.0
+            } else {
+                0.0
+            };
+            (xs[1].at, flow * sign)
+        })
+        .collect();
+
+    Ok(flows
+        .windows(duration)
+        .map(|xs| {
+            let positive: f64 = xs.iter().map(|x| x.1.max(0.0)).sum();
+            let negative: f64 = xs.iter().map(|x| (-x.1).max(0.0)).sum();
+            IndexEntry {
+                at: xs.last().unwrap().0,
+                value: if positive + negative == 0.0 {
+                    50.0
+                } else {
+                    positive / (positive + negative) * 100.0
+                },
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Candlestick;
+
+    #[test]
+    fn test_mfi() {
+        let xs = vec![
+            Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1200.0),
+            Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 800.0),
+            Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1500.0),
+            Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 900.0),
+        ];
+        let got = super::mfi(&xs, 3).unwrap();
+        assert_eq!(2, got.len());
+        assert_eq!(1719400004, got[0].at);
+        assert!((got[0].value - 61.92259675405742).abs() < 1e-9);
+        assert_eq!(1719400005, got[1].at);
+        assert!((got[1].value - 30.47851264858275).abs() < 1e-9);
+
+        assert!(super::mfi(&xs, 5).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_mfi_without_money_flow() {
+        // zero-volume bars such as those filled in gaps
+        let xs = vec![
+            Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 0.0),
+            Candlestick::new(1719400003, 130.0, 130.0, 130.0, 130.0, 0.0),
+            Candlestick::new(1719400004, 130.0, 140.0, 120.0, 135.0, 1000.0),
+        ];
+        let got = super::mfi(&xs, 2).unwrap();
+        assert_eq!(2, got.len());
+        assert_eq!(50.0, got[0].value);
+        assert_eq!(100.0, got[1].value);
+    }
+}
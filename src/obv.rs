@@ -0,0 +1,70 @@
+//! OBV (On-Balance Volume)
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get OBV calculation result
+//! let result = m4rs::obv(&candlesticks);
+//! ```
+
+use crate::{Candlestick, Error, IndexEntry};
+
+/// Returns OBV (On-Balance Volume) for given Candlestick list, starting from 0
+pub fn obv(entries: &[Candlestick]) -> Result<Vec<IndexEntry>, Error> {
+    if entries.is_empty() {
+        return Ok(vec![]);
+    }
+    Candlestick::validate_list(entries)?;
+
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.at);
+
+    let first = IndexEntry {
+        at: sorted[0].at,
+        value: 0.0,
+    };
+    Ok([first.clone()]
+        .into_iter()
+        .chain(sorted.windows(2).scan(first, |z, xs| {
+            let (prev, x) = (&xs[0], &xs[1]);
+            z.at = x.at;
+            if x.close > prev.close {
+                z.value += x.volume;
+            } else if x.close < prev.close {
+                z.value -= x.volume;
+            }
+            Some(z.clone())
+        }))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Candlestick;
+
+    #[test]
+    fn test_obv() {
+        let xs = vec![
+            Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1200.0),
+            Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 800.0),
+            Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1500.0),
+            Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 900.0),
+        ];
+        let got = super::obv(&xs).unwrap();
+        assert_eq!(5, got.len());
+        assert_eq!(1719400001, got[0].at);
+        assert_eq!(
+            vec![0.0, 1200.0, 400.0, -1100.0, -2000.0],
+            got.iter().map(|x| x.value).collect::<Vec<f64>>()
+        );
+    }
+}
@@ -34,7 +34,7 @@ pub fn parabolic_sar(
     Candlestick::validate_list(entries)?;
 
     let mut sorted = entries.to_owned();
-    sorted.sort_by(|a, b| a.at.cmp(&b.at));
+    sorted.sort_by_key(|x| x.at);
 
     let head = sorted.first().unwrap();
     let mut is_bullish = head.is_bullish();
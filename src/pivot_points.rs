@@ -0,0 +1,423 @@
+//! Pivot Points (Classic, Woodie, Camarilla, Fibonacci, DeMark)
+//!
+//! Levels are derived from a candlestick of a higher timeframe such as a day or a week,
+//! and used as support and resistance during the following period of it.
+//! Timestamps are seconds since the Unix epoch.
+//!
+//! # Examples
+//! ```rust
+//! // Prepare daily candlesticks in some way
+//! let days = vec![
+//!     m4rs::Candlestick::new(1719360000, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719446400, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//! ];
+//!
+//! // Prepare hourly candlesticks in some way
+//! let hours = vec![
+//!     m4rs::Candlestick::new(1719446400, 110.0, 115.0, 105.0, 112.0, 1000.0),
+//!     m4rs::Candlestick::new(1719450000, 112.0, 120.0, 110.0, 118.0, 1000.0),
+//! ];
+//!
+//! // Get Camarilla levels of each day
+//! let result = m4rs::pivot_points(&days, m4rs::PivotKind::Camarilla);
+//!
+//! // Get classic pivot points of the previous day for each hour
+//! let result = m4rs::pivot_points_on(&days, 24 * 60 * 60, &hours, m4rs::PivotKind::Classic);
+//!
+//! // Get classic pivot points of the previous day in UTC+9 from hourly candlesticks only
+//! let result = m4rs::daily_pivot_points(&hours, 9 * 60 * 60, m4rs::PivotKind::Classic);
+//! ```
+
+use std::fmt::Display;
+
+use crate::{Candlestick, Error, IndexEntryLike};
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Formula of pivot points
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PivotKind {
+    /// Floor pivot of (high + low + close) / 3 with 3 levels
+    Classic,
+    /// Pivot weighting close twice with 4 levels
+    Woodie,
+    /// Levels around close by 1.1 times range divided by 12, 6, 4 and 2
+    Camarilla,
+    /// Levels around floor pivot by 0.382, 0.618 and 1 times range
+    Fibonacci,
+    /// Pivot weighted by the direction from open to close with 1 level
+    DeMark,
+}
+
+/// Pivot point with resistance (r) and support (s) levels numbered from the nearest one
+///
+/// Levels the kind does not define are None.
+#[derive(Clone, Debug)]
+pub struct PivotPointsEntry {
+    pub at: u64,
+    pub pivot: f64,
+    pub r1: f64,
+    pub r2: Option<f64>,
+    pub r3: Option<f64>,
+    pub r4: Option<f64>,
+    pub s1: f64,
+    pub s2: Option<f64>,
+    pub s3: Option<f64>,
+    pub s4: Option<f64>,
+}
+
+impl Display for PivotPointsEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "PivotPoints(at={} pivot={} r1={} r2={:?} r3={:?} r4={:?} s1={} s2={:?} s3={:?} s4={:?})",
+            self.at,
+            self.pivot,
+            self.r1,
+            self.r2,
+            self.r3,
+            self.r4,
+            self.s1,
+            self.s2,
+            self.s3,
+            self.s4,
+        )
+    }
+}
+
+impl IndexEntryLike for PivotPointsEntry {
+    fn get_at(&self) -> u64 {
+        self.at
+    }
+
+    fn get_value(&self) -> f64 {
+        self.pivot
+    }
+}
+
+impl PivotPointsEntry {
+    /// Returns levels of given period Candlestick
+    pub fn new(period: &Candlestick, kind: PivotKind) -> Self {
+        let (h, l, c) = (period.high, period.low, period.close);
+        let range = h - l;
+        let floor = (h + l + c) / 3.0;
+        match kind {
+            PivotKind::Classic => PivotPointsEntry {
+                at: period.at,
+                pivot: floor,
+                r1: 2.0 * floor - l,
+                r2: Some(floor + range),
+                r3: Some(h + 2.0 * (floor - l)),
+                r4: None,
+                s1: 2.0 * floor - h,
+                s2: Some(floor - range),
+                s3: Some(l - 2.0 * (h - floor)),
+                s4: None,
+            },
+            PivotKind::Woodie => {
+                let pivot = (h + l + 2.0 * c) / 4.0;
+                let r3 = h + 2.0 * (pivot - l);
+                let s3 = l - 2.0 * (h - pivot);
+                PivotPointsEntry {
+                    at: period.at,
+                    pivot,
+                    r1: 2.0 * pivot - l,
+                    r2: Some(pivot + range),
+                    r3: Some(r3),
+                    r4: Some(r3 + range),
+                    s1: 2.0 * pivot - h,
+                    s2: Some(pivot - range),
+                    s3: Some(s3),
+                    s4: Some(s3 - range),
+                }
+            }
+            PivotKind::Camarilla => {
+                let width = range * 1.1;
+                PivotPointsEntry {
+                    at: period.at,
+                    pivot: floor,
+                    r1: c + width / 12.0,
+                    r2: Some(c + width / 6.0),
+                    r3: Some(c + width / 4.0),
+                    r4: Some(c + width / 2.0),
+                    s1: c - width / 12.0,
+                    s2: Some(c - width / 6.0),
+                    s3: Some(c - width / 4.0),
+                    s4: Some(c - width / 2.0),
+                }
+            }
+            PivotKind::Fibonacci => PivotPointsEntry {
+                at: period.at,
+                pivot: floor,
+                r1: floor + range * 0.382,
+                r2: Some(floor + range * 0.618),
+                r3: Some(floor + range),
+                r4: None,
+                s1: floor - range * 0.382,
+                s2: Some(floor - range * 0.618),
+                s3: Some(floor - range),
+                s4: None,
+            },
+            PivotKind::DeMark => {
+                let x = if c < period.open {
+                    h + 2.0 * l + c
+                } else if c > period.open {
+                    2.0 * h + l + c
+                } else {
+                    h + l + 2.0 * c
+                };
+                PivotPointsEntry {
+                    at: period.at,
+                    pivot: x / 4.0,
+                    r1: x / 2.0 - l,
+                    r2: None,
+                    r3: None,
+                    r4: None,
+                    s1: x / 2.0 - h,
+                    s2: None,
+                    s3: None,
+                    s4: None,
+                }
+            }
+        }
+    }
+}
+
+/// Returns pivot points derived from each period Candlestick, at the time of the period
+pub fn pivot_points(
+    periods: &[Candlestick],
+    kind: PivotKind,
+) -> Result<Vec<PivotPointsEntry>, Error> {
+    Candlestick::validate_list(periods)?;
+
+    let mut sorted = periods.to_owned();
+    sorted.sort_by_key(|x| x.at);
+
+    Ok(sorted
+        .iter()
+        .map(|x| PivotPointsEntry::new(x, kind))
+        .collect())
+}
+
+/// Returns pivot points of the previous period for each entry of lower timeframe
+///
+/// `at` of periods is the start of them and each of them lasts `period` seconds, so an entry
+/// in the latest period starting at or before it gets levels of the period before that one,
+/// and an entry after the end of that period gets levels of that period itself.
+/// Entries in the first period or before it are skipped.
+pub fn pivot_points_on(
+    periods: &[Candlestick],
+    period: u64,
+    entries: &[impl IndexEntryLike],
+    kind: PivotKind,
+) -> Result<Vec<PivotPointsEntry>, Error> {
+    let levels = pivot_points(periods, kind)?;
+
+    let mut ats: Vec<u64> = entries.iter().map(|x| x.get_at()).collect();
+    ats.sort();
+
+    Ok(ats
+        .iter()
+        .filter_map(|at| {
+            let mut i = levels.partition_point(|x| x.at <= *at);
+            if i > 0 && *at < levels[i - 1].at.saturating_add(period) {
+                i -= 1;
+            }
+            (i >= 1).then(|| PivotPointsEntry {
+                at: *at,
+                ..levels[i - 1].clone()
+            })
+        })
+        .collect())
+}
+
+/// Returns pivot points of the previous day for each Candlestick, aggregating them into days
+///
+/// Days start at midnight of the time zone `utc_offset` seconds ahead of UTC, as session VWAP.
+pub fn daily_pivot_points(
+    entries: &[Candlestick],
+    utc_offset: i64,
+    kind: PivotKind,
+) -> Result<Vec<PivotPointsEntry>, Error> {
+    Candlestick::validate_list(entries)?;
+
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.at);
+
+    let day = |at: u64| (at as i64 + utc_offset).div_euclid(SECONDS_PER_DAY);
+    let days: Vec<Candlestick> = sorted
+        .chunk_by(|a, b| day(a.at) == day(b.at))
+        .map(|xs| {
+            let (first, last) = (xs.first().unwrap(), xs.last().unwrap());
+            Candlestick::new(
+                first.at,
+                first.open,
+                xs.iter().map(|x| x.high).reduce(f64::max).unwrap(),
+                xs.iter().map(|x| x.low).reduce(f64::min).unwrap(),
+                last.close,
+                xs.iter().map(|x| x.volume).sum(),
+            )
+        })
+        .collect();
+    pivot_points_on(&days, SECONDS_PER_DAY as u64, &sorted, kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PivotKind;
+    use crate::{Candlestick, IndexEntry};
+
+    fn assert_levels(got: &super::PivotPointsEntry, expected: [f64; 9]) {
+        let levels = [
+            Some(got.pivot),
+            Some(got.r1),
+            got.r2,
+            got.r3,
+            got.r4,
+            Some(got.s1),
+            got.s2,
+            got.s3,
+            got.s4,
+        ];
+        for (x, y) in expected.iter().zip(levels) {
+            if x.is_nan() {
+                assert_eq!(None, y, "{}", got);
+            } else {
+                assert!((x - y.unwrap()).abs() < 1e-9, "{}", got);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pivot_points() {
+        let nan = f64::NAN;
+        let xs = vec![
+            Candlestick::new(1719446400, 110.0, 140.0, 100.0, 130.0, 1000.0),
+            Candlestick::new(1719360000, 100.0, 130.0, 90.0, 110.0, 1000.0),
+        ];
+        let got = super::pivot_points(&xs, PivotKind::Classic).unwrap();
+        assert_eq!(2, got.len());
+        assert_eq!(1719360000, got[0].at);
+        assert_levels(
+            &got[0],
+            [110.0, 130.0, 150.0, 170.0, nan, 90.0, 70.0, 50.0, nan],
+        );
+
+        let got = super::pivot_points(&xs, PivotKind::Woodie).unwrap();
+        assert_levels(
+            &got[1],
+            [125.0, 150.0, 165.0, 190.0, 230.0, 110.0, 85.0, 70.0, 30.0],
+        );
+
+        let got = super::pivot_points(&xs, PivotKind::Camarilla).unwrap();
+        assert_levels(
+            &got[0],
+            [
+                110.0,
+                113.66666666666667,
+                117.33333333333333,
+                121.0,
+                132.0,
+                106.33333333333333,
+                102.66666666666667,
+                99.0,
+                88.0,
+            ],
+        );
+
+        let got = super::pivot_points(&xs, PivotKind::Fibonacci).unwrap();
+        assert_levels(
+            &got[0],
+            [110.0, 125.28, 134.72, 150.0, nan, 94.72, 85.28, 70.0, nan],
+        );
+
+        // close above open
+        let got = super::pivot_points(&xs, PivotKind::DeMark).unwrap();
+        assert_levels(&got[0], [115.0, 140.0, nan, nan, nan, 100.0, nan, nan, nan]);
+        // close below open
+        let got = super::pivot_points(
+            &[Candlestick::new(1, 120.0, 130.0, 90.0, 110.0, 1000.0)],
+            PivotKind::DeMark,
+        )
+        .unwrap();
+        assert_levels(&got[0], [105.0, 120.0, nan, nan, nan, 80.0, nan, nan, nan]);
+    }
+
+    #[test]
+    fn test_pivot_points_on() {
+        let days = vec![
+            Candlestick::new(1719360000, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719446400, 110.0, 140.0, 100.0, 130.0, 1000.0),
+            Candlestick::new(1719532800, 130.0, 135.0, 120.0, 120.0, 1000.0),
+        ];
+        let xs = vec![
+            IndexEntry {
+                at: 1719400000,
+                value: 1.0,
+            },
+            IndexEntry {
+                at: 1719446400,
+                value: 1.0,
+            },
+            IndexEntry {
+                at: 1719500000,
+                value: 1.0,
+            },
+            IndexEntry {
+                at: 1719600000,
+                value: 1.0,
+            },
+        ];
+        let got = super::pivot_points_on(&days, 86400, &xs, PivotKind::Classic).unwrap();
+        assert_eq!(3, got.len());
+        assert_eq!((1719446400, 110.0), (got[0].at, got[0].pivot));
+        assert_eq!((1719500000, 110.0), (got[1].at, got[1].pivot));
+        assert_eq!(1719600000, got[2].at);
+        assert!((got[2].pivot - 123.33333333333333).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_pivot_points_on_after_last_period() {
+        // completed days and bars of today without its daily candlestick yet
+        let days = vec![
+            Candlestick::new(1719360000, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719446400, 110.0, 140.0, 100.0, 130.0, 1000.0),
+        ];
+        let xs = vec![
+            IndexEntry {
+                at: 1719532800,
+                value: 1.0,
+            },
+            IndexEntry {
+                at: 1719600000,
+                value: 1.0,
+            },
+        ];
+        let got = super::pivot_points_on(&days, 86400, &xs, PivotKind::Classic).unwrap();
+        assert_eq!(2, got.len());
+        assert_eq!((1719532800, 370.0 / 3.0), (got[0].at, got[0].pivot));
+        assert_eq!((1719600000, 370.0 / 3.0), (got[1].at, got[1].pivot));
+    }
+
+    #[test]
+    fn test_daily_pivot_points() {
+        // 12:00, 18:00 and 23:00 on 2024-06-26 and 01:00 on 2024-06-27 in UTC
+        let xs = vec![
+            Candlestick::new(1719403200, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719424800, 110.0, 140.0, 100.0, 130.0, 1000.0),
+            Candlestick::new(1719442800, 130.0, 135.0, 120.0, 120.0, 1000.0),
+            Candlestick::new(1719450000, 120.0, 130.0, 80.0, 95.0, 1000.0),
+        ];
+        let got = super::daily_pivot_points(&xs, 0, PivotKind::Classic).unwrap();
+        assert_eq!(1, got.len());
+        assert_eq!(1719450000, got[0].at);
+        // high 140, low 90 and close 120
+        assert_eq!(350.0 / 3.0, got[0].pivot);
+
+        // 23:00 in UTC is the next day in UTC+2
+        let got = super::daily_pivot_points(&xs, 2 * 60 * 60, PivotKind::Classic).unwrap();
+        assert_eq!(2, got.len());
+        assert_eq!(1719442800, got[0].at);
+        assert_eq!(120.0, got[0].pivot);
+    }
+}
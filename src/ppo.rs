@@ -0,0 +1,129 @@
+//! PPO (Percentage Price Oscillator)
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get PPO calculation result
+//! let result = m4rs::ppo(&candlesticks, 12, 26, 9);
+//! ```
+
+use std::fmt::Display;
+
+use crate::{ema, index_entry::zip_tails, Error, IndexEntry, IndexEntryLike};
+
+#[derive(Debug, Clone)]
+pub struct PpoEntry {
+    pub at: u64,
+    pub ppo: f64,
+    pub signal: f64,
+    pub histogram: f64,
+}
+
+impl Display for PpoEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "PPO(at={} ppo={} sig={} hist={})",
+            self.at, self.ppo, self.signal, self.histogram
+        )
+    }
+}
+
+impl IndexEntryLike for PpoEntry {
+    fn get_at(&self) -> u64 {
+        self.at
+    }
+
+    fn get_value(&self) -> f64 {
+        self.ppo
+    }
+}
+
+/// Returns PPO, difference between short and long EMAs in percent of the long one, for given IndexEntry list
+pub fn ppo(
+    entries: &[impl IndexEntryLike],
+    short_duration: usize,
+    long_duration: usize,
+    signal_duration: usize,
+) -> Result<Vec<PpoEntry>, Error> {
+    if long_duration < short_duration {
+        return Err(Error::LongDurationIsNotGreaterThanShortDuration {
+            short_duration,
+            long_duration,
+        });
+    }
+    if entries.is_empty() || short_duration == 0 || long_duration == 0 || signal_duration == 0 {
+        return Ok(vec![]);
+    }
+    let ema_s = ema(entries, short_duration)?;
+    let ema_l = ema(entries, long_duration)?;
+    let ppos: Vec<IndexEntry> = zip_tails(&ema_s, &ema_l)
+        .map(|(s, l)| {
+            if l.value == 0.0 {
+                return Err(Error::DividedByZero {
+                    at: l.at,
+                    field: "long EMA".to_string(),
+                });
+            }
+            Ok(IndexEntry {
+                at: l.at,
+                value: (s.value - l.value) / l.value * 100.0,
+            })
+        })
+        .collect::<Result<_, _>>()?;
+    let signals = ema(&ppos, signal_duration)?;
+    Ok(zip_tails(&ppos, &signals)
+        .map(|(x, signal)| PpoEntry {
+            at: x.at,
+            ppo: x.value,
+            signal: signal.value,
+            histogram: x.value - signal.value,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, IndexEntry};
+
+    #[test]
+    fn test_ppo() {
+        let xs = vec![
+            IndexEntry::new(1719400001, 110.0),
+            IndexEntry::new(1719400002, 130.0),
+            IndexEntry::new(1719400003, 120.0),
+            IndexEntry::new(1719400004, 95.0),
+            IndexEntry::new(1719400005, 82.0),
+            IndexEntry::new(1719400006, 115.0),
+            IndexEntry::new(1719400007, 145.0),
+            IndexEntry::new(1719400008, 155.0),
+            IndexEntry::new(1719400009, 150.0),
+            IndexEntry::new(1719400010, 160.0),
+            IndexEntry::new(1719400011, 140.0),
+            IndexEntry::new(1719400012, 135.0),
+            IndexEntry::new(1719400013, 150.0),
+            IndexEntry::new(1719400014, 165.0),
+        ];
+        let got = super::ppo(&xs, 2, 3, 2).unwrap();
+        assert_eq!(9, got.len());
+        assert_eq!(1719400006, got[0].at);
+        assert!((got[0].ppo - 1.425859709530743).abs() < 1e-9);
+        assert!((got[0].signal - -0.6873106789661216).abs() < 1e-9);
+        assert!((got[0].histogram - 2.1131703884968647).abs() < 1e-9);
+        assert!((got[8].ppo - 2.297637257215832).abs() < 1e-9);
+        assert!((got[8].histogram - 0.7263988221634765).abs() < 1e-9);
+
+        assert!(matches!(
+            super::ppo(&xs, 3, 2, 2),
+            Err(Error::LongDurationIsNotGreaterThanShortDuration { .. })
+        ));
+    }
+}
@@ -0,0 +1,1655 @@
+//! Indicator registry driven by string specs
+//!
+//! Parses specs such as `ema(20)` or `bollinger(20, 2.0)`, validates their
+//! parameters and computes the indicator with a uniform multi-column output.
+//! Each indicator also describes how to chart it (pane, bounds, reference
+//! levels, fills and plot style of outputs) so front-ends can render any of them.
+//! `name:20,2.0` is accepted as well, and omitted trailing parameters fall
+//! back to their defaults.
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Parse a spec and compute it
+//! let spec: m4rs::IndicatorSpec = "bollinger(3, 2.0)".parse().unwrap();
+//! let output = spec.compute(&candlesticks).unwrap();
+//! assert_eq!(vec!["avg", "upper", "lower"], output.names);
+//!
+//! // Look up metadata of an indicator
+//! let info = m4rs::find_indicator("macd").unwrap();
+//! assert_eq!("fast", info.params[0].name);
+//!
+//! // Render any indicator from its chart metadata
+//! let info = m4rs::find_indicator("rsi").unwrap();
+//! assert_eq!(m4rs::Pane::Separate, info.chart.pane);
+//! assert_eq!(Some((0.0, 100.0)), info.chart.bounds);
+//! assert_eq!(&[30.0, 70.0], info.chart.levels);
+//! ```
+
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use crate::{Candlestick, Error, IndexEntry};
+
+/// Type of an indicator parameter
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParamKind {
+    /// Positive integer such as a period
+    Duration,
+    /// Finite real number
+    Float,
+}
+
+/// Typed parameter value
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ParamValue {
+    Duration(usize),
+    Float(f64),
+}
+
+impl ParamValue {
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            ParamValue::Duration(x) => *x as f64,
+            ParamValue::Float(x) => *x,
+        }
+    }
+}
+
+impl Display for ParamValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParamValue::Duration(x) => write!(f, "{}", x),
+            ParamValue::Float(x) => write!(f, "{:?}", x),
+        }
+    }
+}
+
+/// Metadata of an indicator parameter
+#[derive(Clone, Debug)]
+pub struct ParamInfo {
+    pub name: &'static str,
+    pub kind: ParamKind,
+    pub default: ParamValue,
+}
+
+const fn duration(name: &'static str, default: usize) -> ParamInfo {
+    ParamInfo {
+        name,
+        kind: ParamKind::Duration,
+        default: ParamValue::Duration(default),
+    }
+}
+
+const fn float(name: &'static str, default: f64) -> ParamInfo {
+    ParamInfo {
+        name,
+        kind: ParamKind::Float,
+        default: ParamValue::Float(default),
+    }
+}
+
+/// Where an indicator is drawn
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pane {
+    /// Over the price chart, sharing its scale
+    Overlay,
+    /// In a pane of its own below the price chart
+    Separate,
+}
+
+/// How an output is drawn
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlotStyle {
+    Line,
+    Histogram,
+    /// Dot at each value, such as Parabolic SAR
+    Dots,
+    /// Marker above the bar's high where the value is non-zero
+    MarkerAbove,
+    /// Marker below the bar's low where the value is non-zero
+    MarkerBelow,
+    /// Part of a candlestick made of open, high, low and close outputs
+    Candlestick,
+}
+
+/// Metadata of an indicator output
+#[derive(Clone, Copy, Debug)]
+pub struct OutputInfo {
+    pub name: &'static str,
+    pub style: PlotStyle,
+}
+
+const fn output(name: &'static str, style: PlotStyle) -> OutputInfo {
+    OutputInfo { name, style }
+}
+
+const fn line(name: &'static str) -> OutputInfo {
+    output(name, PlotStyle::Line)
+}
+
+const fn histogram(name: &'static str) -> OutputInfo {
+    output(name, PlotStyle::Histogram)
+}
+
+const fn candle(name: &'static str) -> OutputInfo {
+    output(name, PlotStyle::Candlestick)
+}
+
+/// Metadata for rendering an indicator generically
+#[derive(Clone, Copy, Debug)]
+pub struct ChartInfo {
+    pub pane: Pane,
+    /// Typical range of values such as `(0.0, 100.0)` for RSI, `None` if unbounded
+    pub bounds: Option<(f64, f64)>,
+    /// Reference levels drawn as horizontal lines such as 30 and 70 for RSI
+    pub levels: &'static [f64],
+    /// Pairs of outputs whose area between is filled, such as the Ichimoku cloud
+    pub fills: &'static [(&'static str, &'static str)],
+}
+
+impl ChartInfo {
+    const fn new(pane: Pane) -> ChartInfo {
+        ChartInfo {
+            pane,
+            bounds: None,
+            levels: &[],
+            fills: &[],
+        }
+    }
+
+    const fn overlay() -> ChartInfo {
+        Self::new(Pane::Overlay)
+    }
+
+    const fn separate() -> ChartInfo {
+        Self::new(Pane::Separate)
+    }
+
+    const fn bounds(self, min: f64, max: f64) -> ChartInfo {
+        ChartInfo {
+            bounds: Some((min, max)),
+            ..self
+        }
+    }
+
+    const fn levels(self, levels: &'static [f64]) -> ChartInfo {
+        ChartInfo { levels, ..self }
+    }
+
+    const fn fills(self, fills: &'static [(&'static str, &'static str)]) -> ChartInfo {
+        ChartInfo { fills, ..self }
+    }
+}
+
+type Calc = fn(&[Candlestick], &Params) -> Result<IndicatorOutput, Error>;
+
+/// Metadata of a registered indicator
+#[derive(Clone, Debug)]
+pub struct IndicatorInfo {
+    pub name: &'static str,
+    pub aliases: &'static [&'static str],
+    pub description: &'static str,
+    pub params: &'static [ParamInfo],
+    pub outputs: &'static [OutputInfo],
+    pub chart: ChartInfo,
+    calc: Calc,
+}
+
+impl IndicatorInfo {
+    fn matches(&self, name: &str) -> bool {
+        self.name == name || self.aliases.contains(&name)
+    }
+}
+
+/// Parameter values passed to the calculation of an indicator
+pub struct Params<'a>(&'a IndicatorInfo, &'a [ParamValue]);
+
+impl Params<'_> {
+    /// Returns duration, rejecting negative or non-integral float values
+    fn duration(&self, i: usize) -> Result<usize, Error> {
+        match self.1[i] {
+            ParamValue::Duration(x) => Ok(x),
+            ParamValue::Float(x) if x >= 0.0 && x.fract() == 0.0 && x <= usize::MAX as f64 => {
+                Ok(x as usize)
+            }
+            ParamValue::Float(x) => Err(Error::InvalidParameter {
+                name: self.0.name.to_string(),
+                param: format!("{}={}", self.0.params[i].name, x),
+            }),
+        }
+    }
+
+    fn float(&self, i: usize) -> f64 {
+        self.1[i].as_f64()
+    }
+}
+
+/// Uniform output of an indicator: rows of values named by `names`
+///
+/// A value is `None` when the column has no value at the row's timestamp.
+#[derive(Clone, Debug)]
+pub struct IndicatorOutput {
+    pub names: Vec<&'static str>,
+    pub rows: Vec<IndicatorRow>,
+}
+
+#[derive(Clone, Debug)]
+pub struct IndicatorRow {
+    pub at: u64,
+    pub values: Vec<Option<f64>>,
+}
+
+type Column<'a, T> = (&'static str, &'a dyn Fn(&T) -> f64);
+
+impl From<Vec<IndexEntry>> for IndicatorOutput {
+    fn from(xs: Vec<IndexEntry>) -> Self {
+        Self::from_rows(&xs, |x| x.at, &[("value", &|x| x.value)])
+    }
+}
+
+impl IndicatorOutput {
+    /// Returns values of given column as IndexEntry list, skipping rows without value
+    pub fn column(&self, name: &str) -> Option<Vec<IndexEntry>> {
+        let i = self.names.iter().position(|x| *x == name)?;
+        Some(
+            self.rows
+                .iter()
+                .filter_map(|row| row.values[i].map(|v| IndexEntry::new(row.at, v)))
+                .collect(),
+        )
+    }
+
+    fn from_rows<T>(xs: &[T], at: impl Fn(&T) -> u64, columns: &[Column<T>]) -> Self {
+        IndicatorOutput {
+            names: columns.iter().map(|(name, _)| *name).collect(),
+            rows: xs
+                .iter()
+                .map(|x| IndicatorRow {
+                    at: at(x),
+                    values: columns.iter().map(|(_, f)| Some(f(x))).collect(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Joins lines having different timestamps into rows sorted by `at`
+    fn from_lines(lines: &[(&'static str, &[IndexEntry])]) -> Self {
+        let mut ats: Vec<u64> = lines
+            .iter()
+            .flat_map(|(_, xs)| xs.iter().map(|x| x.at))
+            .collect();
+        ats.sort();
+        ats.dedup();
+        let columns: Vec<HashMap<u64, f64>> = lines
+            .iter()
+            .map(|(_, xs)| xs.iter().map(|x| (x.at, x.value)).collect())
+            .collect();
+        IndicatorOutput {
+            names: lines.iter().map(|(name, _)| *name).collect(),
+            rows: ats
+                .iter()
+                .map(|at| IndicatorRow {
+                    at: *at,
+                    values: columns.iter().map(|xs| xs.get(at).cloned()).collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+const LINE: &[OutputInfo] = &[line("value")];
+
+/// Registered indicators
+pub static INDICATORS: &[IndicatorInfo] = &[
+    IndicatorInfo {
+        name: "sma",
+        aliases: &[],
+        description: "Simple Moving Average",
+        params: &[duration("period", 20)],
+        outputs: LINE,
+        chart: ChartInfo::overlay(),
+        calc: |xs, p| crate::sma(xs, p.duration(0)?).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "ema",
+        aliases: &[],
+        description: "Exponential Moving Average",
+        params: &[duration("period", 20)],
+        outputs: LINE,
+        chart: ChartInfo::overlay(),
+        calc: |xs, p| crate::ema(xs, p.duration(0)?).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "wma",
+        aliases: &[],
+        description: "Weighted Moving Average",
+        params: &[duration("period", 20)],
+        outputs: LINE,
+        chart: ChartInfo::overlay(),
+        calc: |xs, p| crate::wma(xs, p.duration(0)?).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "rma",
+        aliases: &[],
+        description: "Running Moving Average",
+        params: &[duration("period", 14)],
+        outputs: LINE,
+        chart: ChartInfo::overlay(),
+        calc: |xs, p| crate::rma(xs, p.duration(0)?).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "dema",
+        aliases: &[],
+        description: "Double Exponential Moving Average",
+        params: &[duration("period", 20)],
+        outputs: LINE,
+        chart: ChartInfo::overlay(),
+        calc: |xs, p| crate::dema(xs, p.duration(0)?).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "tema",
+        aliases: &[],
+        description: "Triple Exponential Moving Average",
+        params: &[duration("period", 20)],
+        outputs: LINE,
+        chart: ChartInfo::overlay(),
+        calc: |xs, p| crate::tema(xs, p.duration(0)?).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "hma",
+        aliases: &[],
+        description: "Hull Moving Average",
+        params: &[duration("period", 20)],
+        outputs: LINE,
+        chart: ChartInfo::overlay(),
+        calc: |xs, p| crate::hma(xs, p.duration(0)?).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "vwma",
+        aliases: &[],
+        description: "Volume Weighted Moving Average",
+        params: &[duration("period", 20)],
+        outputs: LINE,
+        chart: ChartInfo::overlay(),
+        calc: |xs, p| crate::vwma(xs, p.duration(0)?).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "kama",
+        aliases: &[],
+        description: "Kaufman Adaptive Moving Average",
+        params: &[
+            duration("period", 10),
+            duration("fast", 2),
+            duration("slow", 30),
+        ],
+        outputs: LINE,
+        chart: ChartInfo::overlay(),
+        calc: |xs, p| {
+            crate::kama(xs, p.duration(0)?, p.duration(1)?, p.duration(2)?)
+                .map(IndicatorOutput::from)
+        },
+    },
+    IndicatorInfo {
+        name: "zlema",
+        aliases: &[],
+        description: "Zero Lag Exponential Moving Average",
+        params: &[duration("period", 20)],
+        outputs: LINE,
+        chart: ChartInfo::overlay(),
+        calc: |xs, p| crate::zlema(xs, p.duration(0)?).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "alma",
+        aliases: &[],
+        description: "Arnaud Legoux Moving Average",
+        params: &[
+            duration("period", 9),
+            float("offset", 0.85),
+            float("sigma", 6.0),
+        ],
+        outputs: LINE,
+        chart: ChartInfo::overlay(),
+        calc: |xs, p| {
+            crate::alma(xs, p.duration(0)?, p.float(1) as f32, p.float(2) as f32)
+                .map(IndicatorOutput::from)
+        },
+    },
+    IndicatorInfo {
+        name: "t3",
+        aliases: &[],
+        description: "Tillson T3 Moving Average",
+        params: &[duration("period", 5), float("volume_factor", 0.7)],
+        outputs: LINE,
+        chart: ChartInfo::overlay(),
+        calc: |xs, p| crate::t3(xs, p.duration(0)?, p.float(1) as f32).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "mcginley_dynamic",
+        aliases: &["mcginley", "md"],
+        description: "McGinley Dynamic",
+        params: &[duration("period", 14)],
+        outputs: LINE,
+        chart: ChartInfo::overlay(),
+        calc: |xs, p| crate::mcginley_dynamic(xs, p.duration(0)?).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "vidya",
+        aliases: &[],
+        description: "Variable Index Dynamic Average",
+        params: &[duration("period", 14)],
+        outputs: LINE,
+        chart: ChartInfo::overlay(),
+        calc: |xs, p| crate::vidya(xs, p.duration(0)?).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "vwap",
+        aliases: &[],
+        description: "Session VWAP with standard deviation bands",
+        params: &[
+            float("utc_offset_hours", 0.0),
+            float("multiplier", 1.0),
+            float("session_start_hours", 0.0),
+        ],
+        outputs: &[line("vwap"), line("upper"), line("lower")],
+        chart: ChartInfo::overlay().fills(&[("upper", "lower")]),
+        calc: |xs, p| {
+            let utc_offset = (p.float(0) * 3600.0).round() as i64;
+            let weight = p.float(1) as f32;
+            let session_start = ((p.float(2) * 3600.0).round() as i64).rem_euclid(86400) as u32;
+            Ok(IndicatorOutput::from_rows(
+                &crate::vwap(xs, utc_offset, session_start)?,
+                |x| x.at,
+                &[
+                    ("vwap", &|x| x.vwap),
+                    ("upper", &|x| x.upper_sigma(weight)),
+                    ("lower", &|x| x.lower_sigma(weight)),
+                ],
+            ))
+        },
+    },
+    IndicatorInfo {
+        name: "momentum",
+        aliases: &["mom"],
+        description: "Momentum",
+        params: &[duration("period", 10)],
+        outputs: LINE,
+        chart: ChartInfo::separate().levels(&[0.0]),
+        calc: |xs, p| crate::momentum(xs, p.duration(0)?).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "roc",
+        aliases: &[],
+        description: "Rate of Change in percent",
+        params: &[duration("period", 9)],
+        outputs: LINE,
+        chart: ChartInfo::separate().levels(&[0.0]),
+        calc: |xs, p| crate::roc(xs, p.duration(0)?).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "trix",
+        aliases: &[],
+        description: "Triple Exponential Average",
+        params: &[duration("period", 18)],
+        outputs: LINE,
+        chart: ChartInfo::separate().levels(&[0.0]),
+        calc: |xs, p| crate::trix(xs, p.duration(0)?).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "ppo",
+        aliases: &[],
+        description: "Percentage Price Oscillator",
+        params: &[
+            duration("fast", 12),
+            duration("slow", 26),
+            duration("signal", 9),
+        ],
+        outputs: &[line("ppo"), line("signal"), histogram("histogram")],
+        chart: ChartInfo::separate().levels(&[0.0]),
+        calc: |xs, p| {
+            Ok(IndicatorOutput::from_rows(
+                &crate::ppo(xs, p.duration(0)?, p.duration(1)?, p.duration(2)?)?,
+                |x| x.at,
+                &[
+                    ("ppo", &|x| x.ppo),
+                    ("signal", &|x| x.signal),
+                    ("histogram", &|x| x.histogram),
+                ],
+            ))
+        },
+    },
+    IndicatorInfo {
+        name: "cmo",
+        aliases: &[],
+        description: "Chande Momentum Oscillator",
+        params: &[duration("period", 9)],
+        outputs: LINE,
+        chart: ChartInfo::separate()
+            .bounds(-100.0, 100.0)
+            .levels(&[-50.0, 50.0]),
+        calc: |xs, p| crate::cmo(xs, p.duration(0)?).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "kst",
+        aliases: &[],
+        description: "Know Sure Thing",
+        params: &[
+            duration("roc1", 10),
+            duration("roc2", 15),
+            duration("roc3", 20),
+            duration("roc4", 30),
+            duration("sma1", 10),
+            duration("sma2", 10),
+            duration("sma3", 10),
+            duration("sma4", 15),
+            duration("signal", 9),
+        ],
+        outputs: &[line("kst"), line("signal")],
+        chart: ChartInfo::separate().levels(&[0.0]),
+        calc: |xs, p| {
+            Ok(IndicatorOutput::from_rows(
+                &crate::kst(
+                    xs,
+                    [
+                        p.duration(0)?,
+                        p.duration(1)?,
+                        p.duration(2)?,
+                        p.duration(3)?,
+                    ],
+                    [
+                        p.duration(4)?,
+                        p.duration(5)?,
+                        p.duration(6)?,
+                        p.duration(7)?,
+                    ],
+                    p.duration(8)?,
+                )?,
+                |x| x.at,
+                &[("kst", &|x| x.kst), ("signal", &|x| x.signal)],
+            ))
+        },
+    },
+    IndicatorInfo {
+        name: "coppock",
+        aliases: &[],
+        description: "Coppock Curve",
+        params: &[
+            duration("period", 10),
+            duration("long_roc", 14),
+            duration("short_roc", 11),
+        ],
+        outputs: LINE,
+        chart: ChartInfo::separate().levels(&[0.0]),
+        calc: |xs, p| {
+            crate::coppock(xs, p.duration(0)?, p.duration(1)?, p.duration(2)?)
+                .map(IndicatorOutput::from)
+        },
+    },
+    IndicatorInfo {
+        name: "rci",
+        aliases: &[],
+        description: "Rank Correlation Index",
+        params: &[duration("period", 9)],
+        outputs: LINE,
+        chart: ChartInfo::separate()
+            .bounds(-100.0, 100.0)
+            .levels(&[-80.0, 80.0]),
+        calc: |xs, p| crate::rci(xs, p.duration(0)?).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "rsi",
+        aliases: &[],
+        description: "Relative Strength Index",
+        params: &[duration("period", 14)],
+        outputs: LINE,
+        chart: ChartInfo::separate()
+            .bounds(0.0, 100.0)
+            .levels(&[30.0, 70.0]),
+        calc: |xs, p| crate::rsi(xs, p.duration(0)?).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "stoch_rsi",
+        aliases: &["stochrsi"],
+        description: "Stochastic RSI",
+        params: &[
+            duration("rsi", 14),
+            duration("stoch", 14),
+            duration("k", 3),
+            duration("d", 3),
+        ],
+        outputs: &[line("k"), line("d")],
+        chart: ChartInfo::separate()
+            .bounds(0.0, 100.0)
+            .levels(&[20.0, 80.0]),
+        calc: |xs, p| {
+            Ok(IndicatorOutput::from_rows(
+                &crate::stoch_rsi(
+                    xs,
+                    p.duration(0)?,
+                    p.duration(1)?,
+                    p.duration(2)?,
+                    p.duration(3)?,
+                )?,
+                |x| x.at,
+                &[("k", &|x| x.k), ("d", &|x| x.d)],
+            ))
+        },
+    },
+    IndicatorInfo {
+        name: "ultimate_oscillator",
+        aliases: &["uo"],
+        description: "Ultimate Oscillator",
+        params: &[
+            duration("short", 7),
+            duration("middle", 14),
+            duration("long", 28),
+        ],
+        outputs: LINE,
+        chart: ChartInfo::separate()
+            .bounds(0.0, 100.0)
+            .levels(&[30.0, 70.0]),
+        calc: |xs, p| {
+            crate::ultimate_oscillator(xs, p.duration(0)?, p.duration(1)?, p.duration(2)?)
+                .map(IndicatorOutput::from)
+        },
+    },
+    IndicatorInfo {
+        name: "connors_rsi",
+        aliases: &["crsi"],
+        description: "Connors RSI",
+        params: &[
+            duration("rsi", 3),
+            duration("streak", 2),
+            duration("rank", 100),
+        ],
+        outputs: LINE,
+        chart: ChartInfo::separate()
+            .bounds(0.0, 100.0)
+            .levels(&[10.0, 90.0]),
+        calc: |xs, p| {
+            Ok(IndicatorOutput::from_rows(
+                &crate::connors_rsi(xs, p.duration(0)?, p.duration(1)?, p.duration(2)?)?,
+                |x| x.at,
+                &[("value", &|x| x.value)],
+            ))
+        },
+    },
+    IndicatorInfo {
+        name: "standard_deviation",
+        aliases: &["stddev", "stdev"],
+        description: "Standard Deviation",
+        params: &[duration("period", 20)],
+        outputs: LINE,
+        chart: ChartInfo::separate(),
+        calc: |xs, p| crate::standard_deviation(xs, p.duration(0)?).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "bolinger_band",
+        aliases: &["bollinger", "bollinger_band", "bollinger_bands", "bb"],
+        description: "Bolinger Band",
+        params: &[duration("period", 20), float("multiplier", 2.0)],
+        outputs: &[line("avg"), line("upper"), line("lower")],
+        chart: ChartInfo::overlay().fills(&[("upper", "lower")]),
+        calc: |xs, p| {
+            let weight = p.float(1) as f32;
+            Ok(IndicatorOutput::from_rows(
+                &crate::bolinger_band(xs, p.duration(0)?)?,
+                |x| x.at,
+                &[
+                    ("avg", &|x| x.avg),
+                    ("upper", &|x| x.upper_sigma(weight)),
+                    ("lower", &|x| x.lower_sigma(weight)),
+                ],
+            ))
+        },
+    },
+    IndicatorInfo {
+        name: "envelope",
+        aliases: &[],
+        description: "Envelope around SMA",
+        params: &[duration("period", 20), float("percent", 10.0)],
+        outputs: &[line("basis"), line("upper"), line("lower")],
+        chart: ChartInfo::overlay().fills(&[("upper", "lower")]),
+        calc: |xs, p| {
+            Ok(IndicatorOutput::from_rows(
+                &crate::envelope(&crate::sma(xs, p.duration(0)?)?, p.float(1) as f32)?,
+                |x| x.at,
+                &[
+                    ("basis", &|x| x.basis),
+                    ("upper", &|x| x.upper),
+                    ("lower", &|x| x.lower),
+                ],
+            ))
+        },
+    },
+    IndicatorInfo {
+        name: "keltner_channel",
+        aliases: &["keltner", "kc"],
+        description: "Keltner Channel around EMA",
+        params: &[
+            duration("period", 20),
+            duration("atr_period", 10),
+            float("multiplier", 2.0),
+        ],
+        outputs: &[line("basis"), line("upper"), line("lower")],
+        chart: ChartInfo::overlay().fills(&[("upper", "lower")]),
+        calc: |xs, p| {
+            Ok(IndicatorOutput::from_rows(
+                &crate::keltner_channel(
+                    xs,
+                    p.duration(0)?,
+                    p.duration(1)?,
+                    p.float(2) as f32,
+                    crate::MaType::Ema,
+                )?,
+                |x| x.at,
+                &[
+                    ("basis", &|x| x.basis),
+                    ("upper", &|x| x.upper),
+                    ("lower", &|x| x.lower),
+                ],
+            ))
+        },
+    },
+    IndicatorInfo {
+        name: "donchian_channel",
+        aliases: &["donchian", "dc"],
+        description: "Donchian Channel",
+        params: &[duration("period", 20)],
+        outputs: &[line("upper"), line("middle"), line("lower")],
+        chart: ChartInfo::overlay().fills(&[("upper", "lower")]),
+        calc: |xs, p| {
+            Ok(IndicatorOutput::from_rows(
+                &crate::donchian_channel(xs, p.duration(0)?)?,
+                |x| x.at,
+                &[
+                    ("upper", &|x| x.upper),
+                    ("middle", &|x| x.middle),
+                    ("lower", &|x| x.lower),
+                ],
+            ))
+        },
+    },
+    IndicatorInfo {
+        name: "supertrend",
+        aliases: &["st"],
+        description: "SuperTrend (up or down line in the current trend)",
+        params: &[duration("atr_period", 10), float("multiplier", 3.0)],
+        outputs: &[line("up"), line("down")],
+        chart: ChartInfo::overlay(),
+        calc: |xs, p| {
+            let xs = crate::supertrend(xs, p.duration(0)?, p.float(1) as f32)?;
+            Ok(IndicatorOutput {
+                names: vec!["up", "down"],
+                rows: xs
+                    .iter()
+                    .map(|x| IndicatorRow {
+                        at: x.at,
+                        values: if x.uptrend {
+                            vec![Some(x.value), None]
+                        } else {
+                            vec![None, Some(x.value)]
+                        },
+                    })
+                    .collect(),
+            })
+        },
+    },
+    IndicatorInfo {
+        name: "macd",
+        aliases: &[],
+        description: "Moving Average Convergence Divergence",
+        params: &[
+            duration("fast", 12),
+            duration("slow", 26),
+            duration("signal", 9),
+        ],
+        outputs: &[line("macd"), line("signal"), histogram("histogram")],
+        chart: ChartInfo::separate().levels(&[0.0]),
+        calc: |xs, p| {
+            Ok(IndicatorOutput::from_rows(
+                &crate::macd(xs, p.duration(0)?, p.duration(1)?, p.duration(2)?)?,
+                |x| x.at,
+                &[
+                    ("macd", &|x| x.macd),
+                    ("signal", &|x| x.signal),
+                    ("histogram", &|x| x.histogram),
+                ],
+            ))
+        },
+    },
+    IndicatorInfo {
+        name: "atr",
+        aliases: &[],
+        description: "Average True Range",
+        params: &[duration("period", 14)],
+        outputs: LINE,
+        chart: ChartInfo::separate(),
+        calc: |xs, p| crate::atr(xs, p.duration(0)?).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "cci",
+        aliases: &[],
+        description: "Commodity Channel Index",
+        params: &[duration("period", 20)],
+        outputs: LINE,
+        chart: ChartInfo::separate().levels(&[-100.0, 100.0]),
+        calc: |xs, p| crate::cci(xs, p.duration(0)?).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "dmi",
+        aliases: &["adx"],
+        description: "Directional Movement Index",
+        params: &[duration("period", 14)],
+        outputs: &[line("plus_di"), line("minus_di"), line("dx"), line("adx")],
+        chart: ChartInfo::separate().bounds(0.0, 1.0).levels(&[0.25]),
+        calc: |xs, p| {
+            Ok(IndicatorOutput::from_rows(
+                &crate::dmi(xs, p.duration(0)?)?,
+                |x| x.at,
+                &[
+                    ("plus_di", &|x| x.plus_di),
+                    ("minus_di", &|x| x.minus_di),
+                    ("dx", &|x| x.dx),
+                    ("adx", &|x| x.adx),
+                ],
+            ))
+        },
+    },
+    IndicatorInfo {
+        name: "dmi_wilder",
+        aliases: &["adx_wilder"],
+        description: "Directional Movement Index with Wilder's smoothing in percentage",
+        params: &[duration("period", 14)],
+        outputs: &[line("plus_di"), line("minus_di"), line("dx"), line("adx")],
+        chart: ChartInfo::separate().bounds(0.0, 100.0).levels(&[25.0]),
+        calc: |xs, p| {
+            Ok(IndicatorOutput::from_rows(
+                &crate::dmi_wilder(xs, p.duration(0)?)?,
+                |x| x.at,
+                &[
+                    ("plus_di", &|x| x.plus_di),
+                    ("minus_di", &|x| x.minus_di),
+                    ("dx", &|x| x.dx),
+                    ("adx", &|x| x.adx),
+                ],
+            ))
+        },
+    },
+    IndicatorInfo {
+        name: "aroon",
+        aliases: &[],
+        description: "Aroon",
+        params: &[duration("period", 14)],
+        outputs: &[line("up"), line("down"), line("oscillator")],
+        chart: ChartInfo::separate().bounds(-100.0, 100.0).levels(&[0.0]),
+        calc: |xs, p| {
+            Ok(IndicatorOutput::from_rows(
+                &crate::aroon(xs, p.duration(0)?)?,
+                |x| x.at,
+                &[
+                    ("up", &|x| x.up),
+                    ("down", &|x| x.down),
+                    ("oscillator", &|x| x.oscillator),
+                ],
+            ))
+        },
+    },
+    IndicatorInfo {
+        name: "vortex",
+        aliases: &["vi"],
+        description: "Vortex Indicator",
+        params: &[duration("period", 14)],
+        outputs: &[line("plus_vi"), line("minus_vi")],
+        chart: ChartInfo::separate().levels(&[1.0]),
+        calc: |xs, p| {
+            Ok(IndicatorOutput::from_rows(
+                &crate::vortex(xs, p.duration(0)?)?,
+                |x| x.at,
+                &[("plus_vi", &|x| x.plus_vi), ("minus_vi", &|x| x.minus_vi)],
+            ))
+        },
+    },
+    IndicatorInfo {
+        name: "choppiness_index",
+        aliases: &["chop"],
+        description: "Choppiness Index",
+        params: &[duration("period", 14)],
+        outputs: LINE,
+        chart: ChartInfo::separate()
+            .bounds(0.0, 100.0)
+            .levels(&[38.2, 61.8]),
+        calc: |xs, p| crate::choppiness_index(xs, p.duration(0)?).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "mass_index",
+        aliases: &[],
+        description: "Mass Index",
+        params: &[duration("ema_period", 9), duration("sum_period", 25)],
+        outputs: LINE,
+        chart: ChartInfo::separate().levels(&[26.5, 27.0]),
+        calc: |xs, p| {
+            crate::mass_index(xs, p.duration(0)?, p.duration(1)?).map(IndicatorOutput::from)
+        },
+    },
+    IndicatorInfo {
+        name: "historical_volatility",
+        aliases: &["hv"],
+        description: "Historical Volatility (Close-to-Close)",
+        params: &[duration("period", 20), float("periods_per_year", 252.0)],
+        outputs: LINE,
+        chart: ChartInfo::separate(),
+        calc: |xs, p| {
+            let estimator = crate::VolatilityEstimator::CloseToClose;
+            crate::historical_volatility(xs, p.duration(0)?, estimator, p.float(1) as f32)
+                .map(IndicatorOutput::from)
+        },
+    },
+    IndicatorInfo {
+        name: "parkinson_volatility",
+        aliases: &[],
+        description: "Historical Volatility (Parkinson)",
+        params: &[duration("period", 20), float("periods_per_year", 252.0)],
+        outputs: LINE,
+        chart: ChartInfo::separate(),
+        calc: |xs, p| {
+            let estimator = crate::VolatilityEstimator::Parkinson;
+            crate::historical_volatility(xs, p.duration(0)?, estimator, p.float(1) as f32)
+                .map(IndicatorOutput::from)
+        },
+    },
+    IndicatorInfo {
+        name: "garman_klass_volatility",
+        aliases: &[],
+        description: "Historical Volatility (Garman-Klass)",
+        params: &[duration("period", 20), float("periods_per_year", 252.0)],
+        outputs: LINE,
+        chart: ChartInfo::separate(),
+        calc: |xs, p| {
+            let estimator = crate::VolatilityEstimator::GarmanKlass;
+            crate::historical_volatility(xs, p.duration(0)?, estimator, p.float(1) as f32)
+                .map(IndicatorOutput::from)
+        },
+    },
+    IndicatorInfo {
+        name: "rogers_satchell_volatility",
+        aliases: &[],
+        description: "Historical Volatility (Rogers-Satchell)",
+        params: &[duration("period", 20), float("periods_per_year", 252.0)],
+        outputs: LINE,
+        chart: ChartInfo::separate(),
+        calc: |xs, p| {
+            let estimator = crate::VolatilityEstimator::RogersSatchell;
+            crate::historical_volatility(xs, p.duration(0)?, estimator, p.float(1) as f32)
+                .map(IndicatorOutput::from)
+        },
+    },
+    IndicatorInfo {
+        name: "yang_zhang_volatility",
+        aliases: &[],
+        description: "Historical Volatility (Yang-Zhang)",
+        params: &[duration("period", 20), float("periods_per_year", 252.0)],
+        outputs: LINE,
+        chart: ChartInfo::separate(),
+        calc: |xs, p| {
+            let estimator = crate::VolatilityEstimator::YangZhang;
+            crate::historical_volatility(xs, p.duration(0)?, estimator, p.float(1) as f32)
+                .map(IndicatorOutput::from)
+        },
+    },
+    IndicatorInfo {
+        name: "stochastics",
+        aliases: &["stoch"],
+        description: "Fast Stochastics",
+        params: &[duration("k", 14), duration("d", 3)],
+        outputs: &[line("k"), line("d")],
+        chart: ChartInfo::separate()
+            .bounds(0.0, 100.0)
+            .levels(&[20.0, 80.0]),
+        calc: |xs, p| {
+            Ok(IndicatorOutput::from_rows(
+                &crate::stochastics(xs, p.duration(0)?, p.duration(1)?)?,
+                |x| x.at,
+                &[("k", &|x| x.k), ("d", &|x| x.d)],
+            ))
+        },
+    },
+    IndicatorInfo {
+        name: "slow_stochastics",
+        aliases: &["slow_stoch"],
+        description: "Slow Stochastics",
+        params: &[duration("k", 14), duration("d", 3), duration("sd", 3)],
+        outputs: &[line("k"), line("d"), line("sd")],
+        chart: ChartInfo::separate()
+            .bounds(0.0, 100.0)
+            .levels(&[20.0, 80.0]),
+        calc: |xs, p| {
+            Ok(IndicatorOutput::from_rows(
+                &crate::slow_stochastics(xs, p.duration(0)?, p.duration(1)?, p.duration(2)?)?,
+                |x| x.at,
+                &[("k", &|x| x.k), ("d", &|x| x.d), ("sd", &|x| x.sd)],
+            ))
+        },
+    },
+    IndicatorInfo {
+        name: "williams_percent_r",
+        aliases: &["willr"],
+        description: "Williams %R",
+        params: &[duration("period", 14)],
+        outputs: LINE,
+        chart: ChartInfo::separate()
+            .bounds(-100.0, 0.0)
+            .levels(&[-80.0, -20.0]),
+        calc: |xs, p| crate::williams_percent_r(xs, p.duration(0)?).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "williams_fractals",
+        aliases: &["fractals"],
+        description: "Williams Fractals (1 if marked, otherwise 0)",
+        params: &[duration("period", 2)],
+        outputs: &[
+            output("up", PlotStyle::MarkerAbove),
+            output("down", PlotStyle::MarkerBelow),
+        ],
+        chart: ChartInfo::overlay(),
+        calc: |xs, p| {
+            let xs = crate::williams_fractals::williams_fractals_or_empty(xs, p.duration(0)?)?;
+            let flag = |x: bool| if x { 1.0 } else { 0.0 };
+            Ok(IndicatorOutput {
+                names: vec!["up", "down"],
+                rows: xs
+                    .iter()
+                    .map(|x| IndicatorRow {
+                        at: x.at,
+                        values: vec![Some(flag(x.up)), Some(flag(x.down))],
+                    })
+                    .collect(),
+            })
+        },
+    },
+    IndicatorInfo {
+        name: "williams_fractals_causal",
+        aliases: &["fractals_causal"],
+        description: "Williams Fractals at the confirming bar (1 if marked, otherwise 0)",
+        params: &[duration("period", 2)],
+        outputs: &[
+            output("up", PlotStyle::MarkerAbove),
+            output("down", PlotStyle::MarkerBelow),
+        ],
+        chart: ChartInfo::overlay(),
+        calc: |xs, p| {
+            let xs = crate::williams_fractals_causal(xs, p.duration(0)?)?;
+            let flag = |x: bool| if x { 1.0 } else { 0.0 };
+            Ok(IndicatorOutput::from_rows(
+                &xs,
+                |x| x.at,
+                &[("up", &|x| flag(x.up)), ("down", &|x| flag(x.down))],
+            ))
+        },
+    },
+    IndicatorInfo {
+        name: "zigzag",
+        aliases: &["zz"],
+        description: "ZigZag (line between confirmed swings reversing by percent)",
+        params: &[float("percent", 5.0)],
+        outputs: LINE,
+        chart: ChartInfo::overlay(),
+        calc: |xs, p| {
+            let threshold = crate::ZigZagThreshold::Percent(p.float(0) as f32);
+            Ok(IndicatorOutput::from_rows(
+                &crate::zigzag(xs, threshold)?,
+                |x| x.at,
+                &[("value", &|x| x.value)],
+            ))
+        },
+    },
+    IndicatorInfo {
+        name: "awesome_oscillator",
+        aliases: &["ao"],
+        description: "Awesome Oscillator",
+        params: &[duration("fast", 5), duration("slow", 34)],
+        outputs: &[histogram("value")],
+        chart: ChartInfo::separate().levels(&[0.0]),
+        calc: |xs, p| {
+            crate::awesome_oscillator(xs, p.duration(0)?, p.duration(1)?).map(IndicatorOutput::from)
+        },
+    },
+    IndicatorInfo {
+        name: "parabolic_sar",
+        aliases: &["psar", "sar"],
+        description: "Parabolic SAR",
+        params: &[
+            float("af_init", 0.02),
+            float("af_step", 0.02),
+            float("af_max", 0.2),
+        ],
+        outputs: &[output("value", PlotStyle::Dots)],
+        chart: ChartInfo::overlay(),
+        calc: |xs, p| {
+            crate::parabolic_sar(xs, p.float(0) as f32, p.float(1) as f32, p.float(2) as f32)
+                .map(IndicatorOutput::from)
+        },
+    },
+    IndicatorInfo {
+        name: "heikin_ashi",
+        aliases: &[],
+        description: "Heikin Ashi",
+        params: &[],
+        outputs: &[
+            candle("open"),
+            candle("high"),
+            candle("low"),
+            candle("close"),
+        ],
+        chart: ChartInfo::separate(),
+        calc: |xs, _| {
+            Ok(IndicatorOutput::from_rows(
+                &crate::heikin_ashi(xs)?,
+                |x| x.at,
+                &[
+                    ("open", &|x| x.open),
+                    ("high", &|x| x.high),
+                    ("low", &|x| x.low),
+                    ("close", &|x| x.close),
+                ],
+            ))
+        },
+    },
+    IndicatorInfo {
+        name: "ichimoku",
+        aliases: &[],
+        description: "Ichimoku Kinko Hyo",
+        params: &[
+            duration("conversion_line", 9),
+            duration("base_line", 26),
+            duration("leading_span_b", 52),
+            duration("lagging_span", 26),
+        ],
+        outputs: &[
+            line("conversion_line"),
+            line("base_line"),
+            line("leading_span_a"),
+            line("leading_span_b"),
+            line("lagging_span"),
+        ],
+        chart: ChartInfo::overlay().fills(&[("leading_span_a", "leading_span_b")]),
+        calc: |xs, p| {
+            let data = crate::ichimoku(
+                xs,
+                p.duration(0)?,
+                p.duration(1)?,
+                p.duration(2)?,
+                p.duration(3)?,
+            )?;
+            Ok(IndicatorOutput::from_lines(&[
+                ("conversion_line", &data.conversion_line),
+                ("base_line", &data.base_line),
+                ("leading_span_a", &data.leading_span_a),
+                ("leading_span_b", &data.leading_span_b),
+                ("lagging_span", &data.lagging_span),
+            ]))
+        },
+    },
+    IndicatorInfo {
+        name: "ichimoku_causal",
+        aliases: &[],
+        description: "Ichimoku Kinko Hyo without displacement, at the time each line is known",
+        params: &[
+            duration("conversion_line", 9),
+            duration("base_line", 26),
+            duration("leading_span_b", 52),
+        ],
+        outputs: &[
+            line("conversion_line"),
+            line("base_line"),
+            line("leading_span_a"),
+            line("leading_span_b"),
+        ],
+        chart: ChartInfo::overlay(),
+        calc: |xs, p| {
+            let data = crate::ichimoku_causal(xs, p.duration(0)?, p.duration(1)?, p.duration(2)?)?;
+            Ok(IndicatorOutput::from_lines(&[
+                ("conversion_line", &data.conversion_line),
+                ("base_line", &data.base_line),
+                ("leading_span_a", &data.leading_span_a),
+                ("leading_span_b", &data.leading_span_b),
+            ]))
+        },
+    },
+    IndicatorInfo {
+        name: "pivot_points",
+        aliases: &["pivots"],
+        description: "Classic Pivot Points of the previous day",
+        params: &[float("utc_offset_hours", 0.0)],
+        outputs: &[
+            line("pivot"),
+            line("r1"),
+            line("r2"),
+            line("r3"),
+            line("s1"),
+            line("s2"),
+            line("s3"),
+        ],
+        chart: ChartInfo::overlay(),
+        calc: |xs, p| {
+            pivot_points(
+                xs,
+                p,
+                crate::PivotKind::Classic,
+                &["pivot", "r1", "r2", "r3", "s1", "s2", "s3"],
+            )
+        },
+    },
+    IndicatorInfo {
+        name: "woodie_pivots",
+        aliases: &[],
+        description: "Woodie Pivot Points of the previous day",
+        params: &[float("utc_offset_hours", 0.0)],
+        outputs: &[
+            line("pivot"),
+            line("r1"),
+            line("r2"),
+            line("r3"),
+            line("r4"),
+            line("s1"),
+            line("s2"),
+            line("s3"),
+            line("s4"),
+        ],
+        chart: ChartInfo::overlay(),
+        calc: |xs, p| {
+            pivot_points(
+                xs,
+                p,
+                crate::PivotKind::Woodie,
+                &["pivot", "r1", "r2", "r3", "r4", "s1", "s2", "s3", "s4"],
+            )
+        },
+    },
+    IndicatorInfo {
+        name: "camarilla_pivots",
+        aliases: &["camarilla"],
+        description: "Camarilla Pivot Points of the previous day",
+        params: &[float("utc_offset_hours", 0.0)],
+        outputs: &[
+            line("pivot"),
+            line("r1"),
+            line("r2"),
+            line("r3"),
+            line("r4"),
+            line("s1"),
+            line("s2"),
+            line("s3"),
+            line("s4"),
+        ],
+        chart: ChartInfo::overlay(),
+        calc: |xs, p| {
+            pivot_points(
+                xs,
+                p,
+                crate::PivotKind::Camarilla,
+                &["pivot", "r1", "r2", "r3", "r4", "s1", "s2", "s3", "s4"],
+            )
+        },
+    },
+    IndicatorInfo {
+        name: "fibonacci_pivots",
+        aliases: &[],
+        description: "Fibonacci Pivot Points of the previous day",
+        params: &[float("utc_offset_hours", 0.0)],
+        outputs: &[
+            line("pivot"),
+            line("r1"),
+            line("r2"),
+            line("r3"),
+            line("s1"),
+            line("s2"),
+            line("s3"),
+        ],
+        chart: ChartInfo::overlay(),
+        calc: |xs, p| {
+            pivot_points(
+                xs,
+                p,
+                crate::PivotKind::Fibonacci,
+                &["pivot", "r1", "r2", "r3", "s1", "s2", "s3"],
+            )
+        },
+    },
+    IndicatorInfo {
+        name: "demark_pivots",
+        aliases: &[],
+        description: "DeMark Pivot Points of the previous day",
+        params: &[float("utc_offset_hours", 0.0)],
+        outputs: &[line("pivot"), line("r1"), line("s1")],
+        chart: ChartInfo::overlay(),
+        calc: |xs, p| pivot_points(xs, p, crate::PivotKind::DeMark, &["pivot", "r1", "s1"]),
+    },
+    IndicatorInfo {
+        name: "obv",
+        aliases: &["on_balance_volume"],
+        description: "On-Balance Volume",
+        params: &[],
+        outputs: LINE,
+        chart: ChartInfo::separate(),
+        calc: |xs, _| crate::obv(xs).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "accumulation_distribution",
+        aliases: &["ad", "adl"],
+        description: "Accumulation/Distribution Line",
+        params: &[],
+        outputs: LINE,
+        chart: ChartInfo::separate(),
+        calc: |xs, _| crate::accumulation_distribution(xs).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "chaikin_money_flow",
+        aliases: &["cmf"],
+        description: "Chaikin Money Flow",
+        params: &[duration("period", 20)],
+        outputs: LINE,
+        chart: ChartInfo::separate().bounds(-1.0, 1.0).levels(&[0.0]),
+        calc: |xs, p| crate::chaikin_money_flow(xs, p.duration(0)?).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "chaikin_oscillator",
+        aliases: &["chaikin"],
+        description: "Chaikin Oscillator",
+        params: &[duration("fast", 3), duration("slow", 10)],
+        outputs: LINE,
+        chart: ChartInfo::separate().levels(&[0.0]),
+        calc: |xs, p| {
+            crate::chaikin_oscillator(xs, p.duration(0)?, p.duration(1)?).map(IndicatorOutput::from)
+        },
+    },
+    IndicatorInfo {
+        name: "mfi",
+        aliases: &["money_flow_index"],
+        description: "Money Flow Index",
+        params: &[duration("period", 14)],
+        outputs: LINE,
+        chart: ChartInfo::separate()
+            .bounds(0.0, 100.0)
+            .levels(&[20.0, 80.0]),
+        calc: |xs, p| crate::mfi(xs, p.duration(0)?).map(IndicatorOutput::from),
+    },
+    IndicatorInfo {
+        name: "force_index",
+        aliases: &["fi"],
+        description: "Force Index",
+        params: &[duration("period", 13)],
+        outputs: LINE,
+        chart: ChartInfo::separate().levels(&[0.0]),
+        calc: |xs, p| crate::force_index(xs, p.duration(0)?).map(IndicatorOutput::from),
+    },
+];
+
+/// Computes daily pivot points keeping levels of given names in the order
+fn pivot_points(
+    xs: &[Candlestick],
+    p: &Params,
+    kind: crate::PivotKind,
+    names: &[&'static str],
+) -> Result<IndicatorOutput, Error> {
+    let utc_offset = (p.float(0) * 3600.0).round() as i64;
+    let xs = crate::daily_pivot_points(xs, utc_offset, kind)?;
+    Ok(IndicatorOutput {
+        names: names.to_vec(),
+        rows: xs
+            .iter()
+            .map(|x| {
+                let levels = [
+                    ("pivot", Some(x.pivot)),
+                    ("r1", Some(x.r1)),
+                    ("r2", x.r2),
+                    ("r3", x.r3),
+                    ("r4", x.r4),
+                    ("s1", Some(x.s1)),
+                    ("s2", x.s2),
+                    ("s3", x.s3),
+                    ("s4", x.s4),
+                ];
+                IndicatorRow {
+                    at: x.at,
+                    values: names
+                        .iter()
+                        .map(|name| levels.iter().find(|(x, _)| x == name).unwrap().1)
+                        .collect(),
+                }
+            })
+            .collect(),
+    })
+}
+
+/// Returns metadata of the indicator with given name or alias (case insensitive)
+pub fn find_indicator(name: &str) -> Option<&'static IndicatorInfo> {
+    let name = name.trim().to_ascii_lowercase();
+    INDICATORS.iter().find(|x| x.matches(&name))
+}
+
+/// Parsed and validated indicator spec
+#[derive(Clone, Debug)]
+pub struct IndicatorSpec {
+    pub info: &'static IndicatorInfo,
+    pub params: Vec<ParamValue>,
+}
+
+impl IndicatorSpec {
+    /// Creates spec from indicator name and parameters, filling omitted trailing ones with defaults
+    pub fn new(name: &str, params: &[f64]) -> Result<IndicatorSpec, Error> {
+        let info = find_indicator(name).ok_or_else(|| Error::UnknownIndicator {
+            name: name.to_string(),
+        })?;
+        if params.len() > info.params.len() {
+            return Err(Error::TooManyParameters {
+                name: info.name.to_string(),
+                max: info.params.len(),
+            });
+        }
+        let params = info
+            .params
+            .iter()
+            .enumerate()
+            .map(|(i, p)| match params.get(i) {
+                None => Ok(p.default),
+                Some(x) => validate_param(info, p, *x),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(IndicatorSpec { info, params })
+    }
+
+    /// Parses spec such as `ema(20)`, `bollinger(20, 2.0)`, `macd:12,26,9` or `ichimoku`
+    pub fn parse(s: &str) -> Result<IndicatorSpec, Error> {
+        let invalid = || Error::InvalidSpec {
+            spec: s.to_string(),
+        };
+        let s = s.trim();
+        let (name, args) = if let Some((name, rest)) = s.split_once('(') {
+            (name, Some(rest.strip_suffix(')').ok_or_else(invalid)?))
+        } else if let Some((name, rest)) = s.split_once(':') {
+            (name, Some(rest))
+        } else {
+            (s, None)
+        };
+        if name.trim().is_empty() {
+            return Err(invalid());
+        }
+        let args: Vec<f64> = match args.map(|x| x.trim()) {
+            None | Some("") => vec![],
+            Some(args) => args
+                .split(',')
+                .map(|x| {
+                    x.trim().parse().map_err(|_| Error::InvalidParameter {
+                        name: name.trim().to_string(),
+                        param: x.trim().to_string(),
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        };
+        Self::new(name, &args)
+    }
+
+    /// Computes the indicator for given Candlestick list
+    pub fn compute(&self, entries: &[Candlestick]) -> Result<IndicatorOutput, Error> {
+        (self.info.calc)(entries, &Params(self.info, &self.params))
+    }
+}
+
+impl FromStr for IndicatorSpec {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        IndicatorSpec::parse(s)
+    }
+}
+
+impl Display for IndicatorSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<String> = self.params.iter().map(|x| x.to_string()).collect();
+        write!(f, "{}({})", self.info.name, params.join(", "))
+    }
+}
+
+fn validate_param(info: &IndicatorInfo, param: &ParamInfo, x: f64) -> Result<ParamValue, Error> {
+    let invalid = || Error::InvalidParameter {
+        name: info.name.to_string(),
+        param: format!("{}={}", param.name, x),
+    };
+    if !x.is_finite() {
+        return Err(invalid());
+    }
+    match param.kind {
+        ParamKind::Duration if x < 1.0 || x.fract() != 0.0 => Err(invalid()),
+        ParamKind::Duration => Ok(ParamValue::Duration(x as usize)),
+        ParamKind::Float => Ok(ParamValue::Float(x)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let got = IndicatorSpec::parse("EMA(20)").unwrap();
+        assert_eq!("ema", got.info.name);
+        assert_eq!(vec![ParamValue::Duration(20)], got.params);
+        assert_eq!("ema(20)", got.to_string());
+
+        let got = IndicatorSpec::parse("bollinger( 10, 2.5 )").unwrap();
+        assert_eq!("bolinger_band", got.info.name);
+        assert_eq!(
+            vec![ParamValue::Duration(10), ParamValue::Float(2.5)],
+            got.params
+        );
+
+        let got = IndicatorSpec::parse("macd:5").unwrap();
+        assert_eq!("macd(5, 26, 9)", got.to_string());
+
+        let got = IndicatorSpec::parse("ichimoku").unwrap();
+        assert_eq!("ichimoku(9, 26, 52, 26)", got.to_string());
+
+        let got = IndicatorSpec::parse("psar()").unwrap();
+        assert_eq!("parabolic_sar(0.02, 0.02, 0.2)", got.to_string());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert!(matches!(
+            IndicatorSpec::parse("foo(1)"),
+            Err(Error::UnknownIndicator { name }) if name == "foo"
+        ));
+        assert!(matches!(
+            IndicatorSpec::parse("ema(20"),
+            Err(Error::InvalidSpec { .. })
+        ));
+        assert!(matches!(
+            IndicatorSpec::parse("ema(x)"),
+            Err(Error::InvalidParameter { param, .. }) if param == "x"
+        ));
+        assert!(matches!(
+            IndicatorSpec::parse("ema(2.5)"),
+            Err(Error::InvalidParameter { param, .. }) if param == "period=2.5"
+        ));
+        assert!(matches!(
+            IndicatorSpec::parse("ema(0)"),
+            Err(Error::InvalidParameter { .. })
+        ));
+        assert!(matches!(
+            IndicatorSpec::parse("rsi(14, 2)"),
+            Err(Error::TooManyParameters { max: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_compute_invalid_duration() {
+        let xs = vec![
+            Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+            Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+        ];
+        let ema = IndicatorSpec::parse("ema(2)").unwrap().info;
+        for (x, expected) in [(2.7, "period=2.7"), (-1.0, "period=-1")] {
+            let spec = IndicatorSpec {
+                info: ema,
+                params: vec![ParamValue::Float(x)],
+            };
+            assert!(matches!(
+                spec.compute(&xs),
+                Err(Error::InvalidParameter { name, param }) if name == "ema" && param == expected
+            ));
+        }
+        let spec = IndicatorSpec {
+            info: ema,
+            params: vec![ParamValue::Float(2.0)],
+        };
+        assert_eq!(1, spec.compute(&xs).unwrap().rows.len());
+    }
+
+    #[test]
+    fn test_compute() {
+        let xs = vec![
+            Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+            Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+            Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+            Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+        ];
+        let got = IndicatorSpec::parse("sma(3)")
+            .unwrap()
+            .compute(&xs)
+            .unwrap();
+        assert_eq!(vec!["value"], got.names);
+        assert_eq!(3, got.rows.len());
+        assert_eq!(1719400004, got.rows[1].at);
+        assert_eq!(vec![Some(115.0)], got.rows[1].values);
+
+        let got = IndicatorSpec::parse("ichimoku(2, 3, 4, 2)")
+            .unwrap()
+            .compute(&xs)
+            .unwrap();
+        assert_eq!(5, got.names.len());
+        let lagging = got.column("lagging_span").unwrap();
+        assert_eq!(5, lagging.len());
+        assert!(got.column("unknown").is_none());
+
+        // every registered indicator outputs the columns it declares
+        for info in INDICATORS {
+            let spec = IndicatorSpec::new(info.name, &[]).unwrap();
+            let got = spec.compute(&xs).unwrap();
+            let names: Vec<&str> = info.outputs.iter().map(|x| x.name).collect();
+            assert_eq!(names, got.names, "{}", info.name);
+            for (a, b) in info.chart.fills {
+                assert!(names.contains(a) && names.contains(b), "{}", info.name);
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! ROC (Rate of Change)
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get 9 bars ROC calculation result
+//! let result = m4rs::roc(&candlesticks, 9);
+//! ```
+
+use crate::{Error, IndexEntry, IndexEntryLike};
+
+/// Returns ROC (Rate of Change) in percent for given IndexEntry list
+pub fn roc(entries: &[impl IndexEntryLike], duration: usize) -> Result<Vec<IndexEntry>, Error> {
+    if duration == 0 || entries.len() <= duration {
+        return Ok(vec![]);
+    }
+    IndexEntry::validate_list(entries)?;
+
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.get_at());
+
+    sorted
+        .iter()
+        .zip(sorted.iter().skip(duration))
+        .map(|(prev, x)| {
+            if prev.get_value() == 0.0 {
+                return Err(Error::DividedByZero {
+                    at: x.get_at(),
+                    field: "previous value".to_string(),
+                });
+            }
+            Ok(IndexEntry {
+                at: x.get_at(),
+                value: (x.get_value() - prev.get_value()) / prev.get_value() * 100.0,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IndexEntry;
+
+    #[test]
+    fn test_roc() {
+        let xs = vec![
+            IndexEntry::new(1719400001, 110.0),
+            IndexEntry::new(1719400002, 130.0),
+            IndexEntry::new(1719400003, 120.0),
+            IndexEntry::new(1719400004, 95.0),
+            IndexEntry::new(1719400005, 82.0),
+            IndexEntry::new(1719400006, 115.0),
+            IndexEntry::new(1719400007, 145.0),
+            IndexEntry::new(1719400008, 155.0),
+            IndexEntry::new(1719400009, 150.0),
+            IndexEntry::new(1719400010, 160.0),
+            IndexEntry::new(1719400011, 140.0),
+            IndexEntry::new(1719400012, 135.0),
+            IndexEntry::new(1719400013, 150.0),
+            IndexEntry::new(1719400014, 165.0),
+        ];
+        let got = super::roc(&xs, 3).unwrap();
+        assert_eq!(11, got.len());
+        assert_eq!(1719400004, got[0].at);
+        assert!((got[0].value - -13.636363636363635).abs() < 1e-9);
+        assert!((got[3].value - 52.63157894736842).abs() < 1e-9);
+        assert!((got[10].value - 17.857142857142858).abs() < 1e-9);
+
+        let xs = vec![IndexEntry::new(1, 0.0), IndexEntry::new(2, 1.0)];
+        assert!(super::roc(&xs, 1).is_err());
+    }
+}
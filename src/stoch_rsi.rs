@@ -0,0 +1,103 @@
+//! Stochastic RSI
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get Stochastic RSI of 14RSI over 14 bars with 3 bars %K and %D
+//! let result = m4rs::stoch_rsi(&candlesticks, 14, 14, 3, 3);
+//! ```
+
+use std::fmt::Display;
+
+use crate::{rsi, slow_stochastics, Error, IndexEntryLike};
+
+#[derive(Clone, Debug)]
+pub struct StochRsiEntry {
+    pub at: u64,
+    /// %K
+    pub k: f64,
+    /// %D
+    pub d: f64,
+}
+
+impl Display for StochRsiEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "StochRsi(at={} k={} d={})", self.at, self.k, self.d)
+    }
+}
+
+impl IndexEntryLike for StochRsiEntry {
+    fn get_at(&self) -> u64 {
+        self.at
+    }
+
+    fn get_value(&self) -> f64 {
+        self.k
+    }
+}
+
+/// Returns Stochastic RSI for given IndexEntry list
+///
+/// Stochastics of RSI over `duration_stoch`, whose %K is smoothed by SMA of
+/// `duration_k` and %D is SMA of %K over `duration_d`.
+pub fn stoch_rsi(
+    entries: &[impl IndexEntryLike],
+    duration_rsi: usize,
+    duration_stoch: usize,
+    duration_k: usize,
+    duration_d: usize,
+) -> Result<Vec<StochRsiEntry>, Error> {
+    // %K and %D of Stochastic RSI are %D and Slow%D of slow stochastics over RSI
+    let rsis = rsi(entries, duration_rsi)?;
+    Ok(
+        slow_stochastics(&rsis, duration_stoch, duration_k, duration_d)?
+            .iter()
+            .map(|x| StochRsiEntry {
+                at: x.at,
+                k: x.d,
+                d: x.sd,
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IndexEntry;
+
+    #[test]
+    fn test_stoch_rsi() {
+        let xs = vec![
+            IndexEntry::new(1719400001, 110.0),
+            IndexEntry::new(1719400002, 130.0),
+            IndexEntry::new(1719400003, 120.0),
+            IndexEntry::new(1719400004, 95.0),
+            IndexEntry::new(1719400005, 82.0),
+            IndexEntry::new(1719400006, 115.0),
+            IndexEntry::new(1719400007, 145.0),
+            IndexEntry::new(1719400008, 155.0),
+            IndexEntry::new(1719400009, 150.0),
+            IndexEntry::new(1719400010, 160.0),
+            IndexEntry::new(1719400011, 140.0),
+            IndexEntry::new(1719400012, 135.0),
+            IndexEntry::new(1719400013, 150.0),
+            IndexEntry::new(1719400014, 165.0),
+        ];
+        let got = super::stoch_rsi(&xs, 3, 3, 2, 2).unwrap();
+        assert_eq!(6, got.len());
+        assert_eq!(1719400009, got[0].at);
+        assert_eq!((50.0, 75.0), (got[0].k, got[0].d));
+        assert!((got[1].k - 37.44010231797277).abs() < 1e-9);
+        assert!((got[1].d - 43.72005115898639).abs() < 1e-9);
+        assert_eq!(0.0, got[3].k);
+        assert_eq!((100.0, 75.0), (got[5].k, got[5].d));
+    }
+}
@@ -20,9 +20,10 @@
 
 use std::fmt::Display;
 
-use crate::{sma, Candlestick, Error, IndexEntry, IndexEntryLike};
+use crate::{Error, HighLowCloseLike, IndexEntry, IndexEntryLike, MaType};
 
 #[derive(Clone, Debug)]
+#[repr(C)]
 pub struct StochasticsEntry {
     pub at: u64,
     /// %K
@@ -48,6 +49,7 @@ impl IndexEntryLike for StochasticsEntry {
 }
 
 #[derive(Clone, Debug)]
+#[repr(C)]
 pub struct SlowStochasticsEntry {
     pub at: u64,
     /// %K
@@ -98,11 +100,21 @@ impl IndexEntryLike for SlowStochasticsEntry {
     }
 }
 
-/// Returns Stochastics for given Candlestick list
+/// Returns Stochastics for given Candlestick or IndexEntry list
 pub fn stochastics(
-    entries: &[Candlestick],
+    entries: &[impl HighLowCloseLike],
     duration_k: usize,
     duration_d: usize,
+) -> Result<Vec<StochasticsEntry>, Error> {
+    stochastics_with_ma(entries, duration_k, duration_d, MaType::Sma)
+}
+
+/// Returns Stochastics whose %D is the moving average selected by `ma`
+pub fn stochastics_with_ma(
+    entries: &[impl HighLowCloseLike],
+    duration_k: usize,
+    duration_d: usize,
+    ma: MaType,
 ) -> Result<Vec<StochasticsEntry>, Error> {
     if duration_k == 0 || duration_d == 0 || entries.len() < duration_k {
         return Ok(vec![]);
@@ -110,7 +122,7 @@ pub fn stochastics(
     let mut sorted = entries.to_owned();
     sorted.sort_by_key(|x| x.get_at());
     let ks = calc_k(&sorted, duration_k);
-    let ds = sma(&ks, duration_d)?;
+    let ds = ma.calc(&ks, duration_d)?;
     Ok(ds
         .iter()
         .filter_map(|d| {
@@ -123,24 +135,35 @@ pub fn stochastics(
         .collect())
 }
 
-/// Returns Slow Stochastics for given Candlestick list
+/// Returns Slow Stochastics for given Candlestick or IndexEntry list
 pub fn slow_stochastics(
-    entries: &[Candlestick],
+    entries: &[impl HighLowCloseLike],
+    duration_k: usize,
+    duration_d: usize,
+    duration_sd: usize,
+) -> Result<Vec<SlowStochasticsEntry>, Error> {
+    slow_stochastics_with_ma(entries, duration_k, duration_d, duration_sd, MaType::Sma)
+}
+
+/// Returns Slow Stochastics whose %D and Slow%D are the moving average selected by `ma`
+pub fn slow_stochastics_with_ma(
+    entries: &[impl HighLowCloseLike],
     duration_k: usize,
     duration_d: usize,
     duration_sd: usize,
+    ma: MaType,
 ) -> Result<Vec<SlowStochasticsEntry>, Error> {
     if duration_k == 0 || duration_d == 0 || duration_sd == 0 || entries.len() < duration_k {
         return Ok(vec![]);
     }
 
-    Candlestick::validate_list(entries)?;
+    IndexEntry::validate_hlc_list(entries)?;
 
     let mut sorted = entries.to_owned();
     sorted.sort_by_key(|x| x.get_at());
     let ks = calc_k(&sorted, duration_k);
-    let ds = sma(&ks, duration_d)?;
-    let sds = sma(&ds, duration_sd)?;
+    let ds = ma.calc(&ks, duration_d)?;
+    let sds = ma.calc(&ds, duration_sd)?;
     Ok(sds
         .iter()
         .map(|sd| SlowStochasticsEntry {
@@ -154,22 +177,75 @@ pub fn slow_stochastics(
         .collect())
 }
 
-fn calc_k(entries: &[Candlestick], duration: usize) -> Vec<IndexEntry> {
+fn calc_k<T: HighLowCloseLike>(entries: &[T], duration: usize) -> Vec<IndexEntry> {
     (0..entries.len() - duration + 1)
         .map(|i| {
-            let xs = entries.iter().skip(i).take(duration);
-            let lowest = xs.clone().map(|x| x.low).reduce(|z, x| z.min(x)).unwrap();
-            let n = xs.clone().map(|x| x.high).reduce(|z, x| z.max(x)).unwrap() - lowest;
-            let last = xs.last().unwrap();
+            let mut xs = entries.iter().skip(i).take(duration);
+            let lowest = xs
+                .clone()
+                .map(|x| x.get_low())
+                .reduce(|z, x| z.min(x))
+                .unwrap();
+            let n = xs
+                .clone()
+                .map(|x| x.get_high())
+                .reduce(|z, x| z.max(x))
+                .unwrap()
+                - lowest;
+            let last = xs.next_back().unwrap();
             let k = if n == 0.0 {
                 0.0
             } else {
-                ((last.close - lowest) / n) * 100.0
+                ((last.get_close() - lowest) / n) * 100.0
             };
             IndexEntry {
-                at: last.at,
+                at: last.get_at(),
                 value: k,
             }
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Candlestick, Error, IndexEntry};
+
+    #[test]
+    fn test_stochastics_of_index_entries() {
+        let xs = vec![
+            IndexEntry::new(1719400001, 110.0),
+            IndexEntry::new(1719400002, 130.0),
+            IndexEntry::new(1719400003, 120.0),
+            IndexEntry::new(1719400004, 95.0),
+            IndexEntry::new(1719400005, 82.0),
+        ];
+        let got = super::stochastics(&xs, 3, 2).unwrap();
+        assert_eq!(2, got.len());
+        assert_eq!(1719400004, got[0].at);
+        assert_eq!(0.0, got[0].k);
+        assert_eq!(25.0, got[0].d);
+        assert_eq!(0.0, got[1].k);
+
+        // same as candlesticks whose high, low and close are the value
+        let candles: Vec<Candlestick> = xs
+            .iter()
+            .map(|x| Candlestick::new(x.at, x.value, x.value, x.value, x.value, 0.0))
+            .collect();
+        let want = super::slow_stochastics(&candles, 2, 2, 2).unwrap();
+        let got = super::slow_stochastics(&xs, 2, 2, 2).unwrap();
+        assert_eq!(want.len(), got.len());
+        assert_eq!(want[0].sd, got[0].sd);
+    }
+
+    #[test]
+    fn test_stochastics_validates_candlesticks() {
+        let xs = vec![
+            Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, f64::INFINITY),
+        ];
+        let got = super::slow_stochastics(&xs, 2, 1, 1);
+        assert!(
+            matches!(got, Err(Error::ContainsInfinite { at: 1719400002, field }) if field == "volume")
+        );
+    }
+}
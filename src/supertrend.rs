@@ -0,0 +1,152 @@
+//! SuperTrend
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get SuperTrend of 3x 10ATR
+//! let result = m4rs::supertrend(&candlesticks, 10, 3.0);
+//! ```
+
+use std::fmt::Display;
+
+use crate::{atr, index_entry::zip_tails, Candlestick, Error, IndexEntryLike};
+
+#[derive(Clone, Debug)]
+pub struct SuperTrendEntry {
+    pub at: u64,
+    /// Trailing band of the current trend: lower band in uptrend, upper band in downtrend
+    pub value: f64,
+    pub upper: f64,
+    pub lower: f64,
+    pub uptrend: bool,
+    /// True if the trend has changed at this entry
+    pub flipped: bool,
+}
+
+impl Display for SuperTrendEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "SuperTrend(at={} value={} upper={} lower={} uptrend={} flipped={})",
+            self.at, self.value, self.upper, self.lower, self.uptrend, self.flipped,
+        )
+    }
+}
+
+impl IndexEntryLike for SuperTrendEntry {
+    fn get_at(&self) -> u64 {
+        self.at
+    }
+
+    fn get_value(&self) -> f64 {
+        self.value
+    }
+}
+
+/// Returns SuperTrend for given Candlestick list
+///
+/// Bands are the median price (high + low) / 2 plus/minus ATR multiplied by `multiplier`,
+/// which only tighten while the previous close stays inside them. The trend starts as
+/// a downtrend and turns when close crosses the band of the current trend.
+pub fn supertrend(
+    entries: &[Candlestick],
+    atr_duration: usize,
+    multiplier: f32,
+) -> Result<Vec<SuperTrendEntry>, Error> {
+    let atrs = atr(entries, atr_duration)?;
+    if atrs.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.at);
+
+    let mut prev: Option<(SuperTrendEntry, f64)> = None;
+    Ok(zip_tails(&sorted, &atrs)
+        .map(|(x, a)| {
+            let median = (x.high + x.low) / 2.0;
+            let width = a.value * multiplier as f64;
+            let (upper, lower) = (median + width, median - width);
+            let entry = match &prev {
+                None => SuperTrendEntry {
+                    at: x.at,
+                    value: upper,
+                    upper,
+                    lower,
+                    uptrend: false,
+                    flipped: false,
+                },
+                Some((p, prev_close)) => {
+                    let upper = if upper < p.upper || *prev_close > p.upper {
+                        upper
+                    } else {
+                        p.upper
+                    };
+                    let lower = if lower > p.lower || *prev_close < p.lower {
+                        lower
+                    } else {
+                        p.lower
+                    };
+                    let uptrend = if p.uptrend {
+                        x.close >= lower
+                    } else {
+                        x.close > upper
+                    };
+                    SuperTrendEntry {
+                        at: x.at,
+                        value: if uptrend { lower } else { upper },
+                        upper,
+                        lower,
+                        uptrend,
+                        flipped: uptrend != p.uptrend,
+                    }
+                }
+            };
+            prev = Some((entry.clone(), x.close));
+            entry
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Candlestick;
+
+    #[test]
+    fn test_supertrend() {
+        let xs = vec![
+            Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+            Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+            Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+            Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+            Candlestick::new(1719400006, 82.0, 120.0, 80.0, 115.0, 1000.0),
+            Candlestick::new(1719400007, 115.0, 150.0, 110.0, 145.0, 1000.0),
+            Candlestick::new(1719400008, 145.0, 160.0, 140.0, 155.0, 1000.0),
+        ];
+        let got = super::supertrend(&xs, 2, 1.0).unwrap();
+        assert_eq!(5, got.len());
+        assert_eq!(1719400004, got[0].at);
+        assert_eq!((143.75, 66.25), (got[0].upper, got[0].lower));
+        assert_eq!(143.75, got[0].value);
+
+        // upper band only tightens in downtrend
+        assert_eq!(119.375, got[1].upper);
+        assert_eq!(119.375, got[2].upper);
+        assert!(!got[2].uptrend);
+
+        // close crosses above upper band
+        assert!(got[3].uptrend && got[3].flipped);
+        assert_eq!(91.40625, got[3].value);
+        assert!(got[4].uptrend && !got[4].flipped);
+        assert_eq!(120.703125, got[4].value);
+    }
+}
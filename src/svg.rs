@@ -0,0 +1,389 @@
+//! SVG rendering of charts
+//!
+//! Output is a standalone SVG string without scripts or external resources.
+//! Coordinates are rounded to two decimal places and elements are written in a
+//! fixed order, so the same input always renders the same string.
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Render Bolinger Band over candlesticks and MACD below them
+//! let svg = m4rs::Chart::new(&candlesticks)
+//!     .indicator("bollinger(3, 2)".parse().unwrap())
+//!     .indicator("macd(2, 3, 2)".parse().unwrap())
+//!     .to_svg(800, 600)
+//!     .unwrap();
+//! assert!(svg.ends_with("</svg>\n"));
+//! ```
+
+use std::fmt::Write;
+
+use crate::{chart::Panel, Chart, Error, PlotStyle};
+
+const COLORS: [&str; 6] = [
+    "#2962ff", "#ff6d00", "#00897b", "#d81b60", "#8e24aa", "#6d4c41",
+];
+const UP_COLOR: &str = "#26a69a";
+const DOWN_COLOR: &str = "#ef5350";
+const GRID_COLOR: &str = "#d0d0d0";
+const TEXT_COLOR: &str = "#404040";
+
+const MARGIN: f64 = 8.0;
+const AXIS_WIDTH: f64 = 56.0;
+const GAP: f64 = 8.0;
+
+impl Chart {
+    /// Renders the chart into an SVG document of given size in pixels
+    pub fn to_svg(&self, width: u32, height: u32) -> Result<String, Error> {
+        let layout = self.layout()?;
+        let (width, height) = (width as f64, height as f64);
+        let left = MARGIN;
+        let plot_width = (width - MARGIN - AXIS_WIDTH).max(1.0);
+        let slot = plot_width / layout.ats.len().max(1) as f64;
+        let x = |i: usize| left + (i as f64 + 0.5) * slot;
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif" font-size="11">"#,
+            w = num(width),
+            h = num(height),
+        );
+        let _ = writeln!(
+            svg,
+            r#"<rect width="{}" height="{}" fill="white"/>"#,
+            num(width),
+            num(height)
+        );
+
+        let gaps = GAP * (layout.panels.len().saturating_sub(1)) as f64;
+        let heights = layout.heights((height - MARGIN * 2.0 - gaps).max(1.0));
+        let mut top = MARGIN;
+        for (panel, h) in layout.panels.iter().zip(heights) {
+            let area = Area {
+                left,
+                top,
+                width: plot_width,
+                height: h,
+                min: panel.min,
+                max: panel.max,
+            };
+            render_panel(&mut svg, panel, &area, slot, &x);
+            top += h + GAP;
+        }
+
+        svg.push_str("</svg>\n");
+        Ok(svg)
+    }
+}
+
+/// Plot area of a panel and its value range
+struct Area {
+    left: f64,
+    top: f64,
+    width: f64,
+    height: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Area {
+    fn y(&self, value: f64) -> f64 {
+        let ratio = (value - self.min) / (self.max - self.min);
+        self.top + self.height * (1.0 - ratio.clamp(0.0, 1.0))
+    }
+}
+
+fn render_panel(
+    svg: &mut String,
+    panel: &Panel,
+    area: &Area,
+    slot: f64,
+    x: &impl Fn(usize) -> f64,
+) {
+    let right = area.left + area.width;
+    let _ = writeln!(
+        svg,
+        r#"<rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="{}"/>"#,
+        num(area.left),
+        num(area.top),
+        num(area.width),
+        num(area.height),
+        GRID_COLOR
+    );
+    for (value, baseline) in [(area.max, "hanging"), (area.min, "auto")] {
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" fill="{}" dominant-baseline="{}">{}</text>"#,
+            num(right + 4.0),
+            num(area.y(value)),
+            TEXT_COLOR,
+            baseline,
+            label(value)
+        );
+    }
+    for level in &panel.levels {
+        let y = num(area.y(*level));
+        let _ = writeln!(
+            svg,
+            r#"<line x1="{}" y1="{y}" x2="{}" y2="{y}" stroke="{}" stroke-dasharray="4 3"/>"#,
+            num(area.left),
+            num(right),
+            GRID_COLOR
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{y}" fill="{}" dominant-baseline="middle">{}</text>"#,
+            num(right + 4.0),
+            TEXT_COLOR,
+            label(*level)
+        );
+    }
+
+    for (a, b) in &panel.fills {
+        let (a, b) = (&panel.series[*a], &panel.series[*b]);
+        let color = COLORS[a.color % COLORS.len()];
+        for range in segments(a.values.iter().zip(&b.values).map(|(a, b)| a.and(*b))) {
+            let upper = range.clone().map(|i| (x(i), area.y(a.values[i].unwrap())));
+            let lower = range.rev().map(|i| (x(i), area.y(b.values[i].unwrap())));
+            let _ = writeln!(
+                svg,
+                r#"<polygon points="{}" fill="{}" fill-opacity="0.15"/>"#,
+                points(upper.chain(lower)),
+                color
+            );
+        }
+    }
+
+    let body = (slot * 0.6).max(1.0);
+    for (i, c) in panel.candles.iter().enumerate() {
+        let Some(c) = c else { continue };
+        let color = if c.close >= c.open {
+            UP_COLOR
+        } else {
+            DOWN_COLOR
+        };
+        let top = area.y(c.open.max(c.close));
+        let bottom = area.y(c.open.min(c.close));
+        let _ = writeln!(
+            svg,
+            r#"<line x1="{x}" y1="{}" x2="{x}" y2="{}" stroke="{color}"/>"#,
+            num(area.y(c.high)),
+            num(area.y(c.low)),
+            x = num(x(i)),
+        );
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{color}"/>"#,
+            num(x(i) - body / 2.0),
+            num(top),
+            num(body),
+            num((bottom - top).max(1.0)),
+        );
+    }
+
+    for series in &panel.series {
+        let color = COLORS[series.color % COLORS.len()];
+        let values = series.values.iter().enumerate();
+        match series.style {
+            PlotStyle::Line | PlotStyle::Candlestick => {
+                for range in segments(series.values.iter().copied()) {
+                    let _ = writeln!(
+                        svg,
+                        r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="1.5"/>"#,
+                        points(range.map(|i| (x(i), area.y(series.values[i].unwrap())))),
+                        color
+                    );
+                }
+            }
+            PlotStyle::Histogram => {
+                let zero = area.y(0.0);
+                for (i, v) in values.filter_map(|(i, v)| Some((i, (*v)?))) {
+                    let y = area.y(v);
+                    let fill = if v >= 0.0 { UP_COLOR } else { DOWN_COLOR };
+                    let _ = writeln!(
+                        svg,
+                        r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                        num(x(i) - body / 2.0),
+                        num(y.min(zero)),
+                        num(body),
+                        num((y - zero).abs()),
+                        fill
+                    );
+                }
+            }
+            PlotStyle::Dots => {
+                for (i, v) in values.filter_map(|(i, v)| Some((i, (*v)?))) {
+                    let _ = writeln!(
+                        svg,
+                        r#"<circle cx="{}" cy="{}" r="2" fill="{}"/>"#,
+                        num(x(i)),
+                        num(area.y(v)),
+                        color
+                    );
+                }
+            }
+            PlotStyle::MarkerAbove | PlotStyle::MarkerBelow => {
+                let (tip, base) = match series.style {
+                    PlotStyle::MarkerAbove => (-4.0, -10.0),
+                    _ => (4.0, 10.0),
+                };
+                for (i, v) in values.filter_map(|(i, v)| Some((i, (*v)?))) {
+                    let (cx, y) = (x(i), area.y(v));
+                    let _ = writeln!(
+                        svg,
+                        r#"<polygon points="{}" fill="{}"/>"#,
+                        points([(cx, y + tip), (cx - 4.0, y + base), (cx + 4.0, y + base)]),
+                        color
+                    );
+                }
+            }
+        }
+    }
+
+    let (mut legend_x, mut legend_y) = (area.left + 4.0, area.top + 4.0);
+    let titles = std::iter::once((panel.title.as_str(), TEXT_COLOR)).chain(
+        panel
+            .series
+            .iter()
+            .filter(|x| panel.title.is_empty() || x.name != panel.title)
+            .map(|x| (x.name.as_str(), COLORS[x.color % COLORS.len()])),
+    );
+    for (text, color) in titles.filter(|(x, _)| !x.is_empty()) {
+        let text_width = text.chars().count() as f64 * 6.5;
+        if legend_x > area.left + 4.0 && legend_x + text_width > right {
+            legend_x = area.left + 4.0;
+            legend_y += 14.0;
+        }
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" fill="{}" dominant-baseline="hanging">{}</text>"#,
+            num(legend_x),
+            num(legend_y),
+            color,
+            escape(text)
+        );
+        legend_x += text_width + 12.0;
+    }
+}
+
+/// Returns ranges of consecutive indices having values
+fn segments(values: impl Iterator<Item = Option<f64>>) -> Vec<std::ops::Range<usize>> {
+    let mut ret = vec![];
+    let mut start = None;
+    let mut len = 0;
+    for (i, v) in values.enumerate() {
+        len = i + 1;
+        match (v, start) {
+            (Some(_), None) => start = Some(i),
+            (None, Some(s)) => {
+                ret.push(s..i);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        ret.push(s..len);
+    }
+    ret
+}
+
+fn points(xs: impl IntoIterator<Item = (f64, f64)>) -> String {
+    xs.into_iter()
+        .map(|(x, y)| format!("{},{}", num(x), num(y)))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Formats a coordinate with at most two decimal places
+fn num(x: f64) -> String {
+    let s = format!("{:.2}", x);
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    if s == "-0" {
+        "0".to_string()
+    } else {
+        s.to_string()
+    }
+}
+
+/// Formats an axis value with precision depending on its magnitude
+pub(crate) fn label(x: f64) -> String {
+    if x.abs() >= 1000.0 {
+        format!("{:.0}", x)
+    } else {
+        num(x)
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Candlestick;
+
+    use super::*;
+
+    #[test]
+    fn test_to_svg() {
+        let xs = vec![
+            Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+            Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+            Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+            Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+        ];
+        let chart = Chart::new(&xs)
+            .indicator("sma(2)".parse().unwrap())
+            .indicator("psar".parse().unwrap())
+            .indicator("ichimoku(2, 2, 3, 2)".parse().unwrap())
+            .indicator("fractals(1)".parse().unwrap())
+            .indicator("macd(2, 3, 2)".parse().unwrap())
+            .indicator("rsi(2)".parse().unwrap());
+        let got = chart.to_svg(400, 300).unwrap();
+        assert_eq!(got, chart.to_svg(400, 300).unwrap());
+        assert!(got.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"400\""));
+        assert!(got.ends_with("</svg>\n"));
+        // Ichimoku cloud between leading spans
+        assert!(got.contains(
+            r##"<polygon points="224,57.08 272,83.18 320,98.84 320,93.62 272,77.96 224,67.52" fill="#8e24aa" fill-opacity="0.15"/>"##
+        ));
+        // fractal marked above the high
+        assert!(got.contains(r##"<polygon points="128,11.31 124,5.31 132,5.31" fill="#ff6d00"/>"##));
+        assert!(got.contains("<circle "));
+        assert!(got.contains("fill-opacity=\"0.15\""));
+        assert!(got.contains(">rsi(2)</text>"));
+        assert!(got.contains(">signal</text>"));
+        assert!(got.contains(">70</text>"));
+
+        let got = Chart::new(&[Candlestick::new(1, f64::NAN, 1.0, 1.0, 1.0, 1.0)]).to_svg(1, 1);
+        assert!(matches!(got, Err(Error::ContainsNaN { .. })));
+    }
+
+    #[test]
+    fn test_segments() {
+        let got = segments([None, Some(1.0), Some(2.0), None, Some(3.0)].into_iter());
+        assert_eq!(vec![1..3, 4..5], got);
+    }
+
+    #[test]
+    fn test_num() {
+        assert_eq!("12.5", num(12.5));
+        assert_eq!("3", num(3.0001));
+        assert_eq!("0", num(-0.001));
+        assert_eq!("-1.25", num(-1.25));
+    }
+}
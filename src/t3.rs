@@ -0,0 +1,95 @@
+//! T3 (Tillson T3 Moving Average)
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get 5T3 with volume factor 0.7 calculation result
+//! let result = m4rs::t3(&candlesticks, 5, 0.7);
+//! ```
+
+use crate::{ema, Error, IndexEntry, IndexEntryLike};
+
+/// Returns T3 (Tillson T3 Moving Average) for given IndexEntry list
+///
+/// Combination of six chained EMAs weighted by `volume_factor`, usually 0.7.
+pub fn t3(
+    entries: &[impl IndexEntryLike],
+    duration: usize,
+    volume_factor: f32,
+) -> Result<Vec<IndexEntry>, Error> {
+    if !volume_factor.is_finite() || volume_factor < 0.0 {
+        return Err(Error::MustBePositiveF32 {
+            value: volume_factor,
+            field: "volume_factor".to_string(),
+        });
+    }
+    let e1 = ema(entries, duration)?;
+    let e2 = ema(&e1, duration)?;
+    let e3 = ema(&e2, duration)?;
+    let e4 = ema(&e3, duration)?;
+    let e5 = ema(&e4, duration)?;
+    let e6 = ema(&e5, duration)?;
+
+    let a = volume_factor as f64;
+    let c1 = -a.powi(3);
+    let c2 = 3.0 * a.powi(2) + 3.0 * a.powi(3);
+    let c3 = -6.0 * a.powi(2) - 3.0 * a - 3.0 * a.powi(3);
+    let c4 = 1.0 + 3.0 * a + a.powi(3) + 3.0 * a.powi(2);
+    // each EMA is a suffix of the previous one, so they are aligned at the end
+    let n = e6.len();
+    Ok(e6
+        .iter()
+        .zip(&e5[e5.len() - n..])
+        .zip(&e4[e4.len() - n..])
+        .zip(&e3[e3.len() - n..])
+        .map(|(((x6, x5), x4), x3)| IndexEntry {
+            at: x6.at,
+            value: c1 * x6.value + c2 * x5.value + c3 * x4.value + c4 * x3.value,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, IndexEntry};
+
+    #[test]
+    fn test_t3() {
+        let xs = vec![
+            IndexEntry::new(1719400001, 110.0),
+            IndexEntry::new(1719400002, 130.0),
+            IndexEntry::new(1719400003, 120.0),
+            IndexEntry::new(1719400004, 95.0),
+            IndexEntry::new(1719400005, 82.0),
+            IndexEntry::new(1719400006, 115.0),
+            IndexEntry::new(1719400007, 145.0),
+            IndexEntry::new(1719400008, 155.0),
+            IndexEntry::new(1719400009, 150.0),
+            IndexEntry::new(1719400010, 160.0),
+            IndexEntry::new(1719400011, 140.0),
+            IndexEntry::new(1719400012, 135.0),
+            IndexEntry::new(1719400013, 150.0),
+            IndexEntry::new(1719400014, 165.0),
+        ];
+        let got = super::t3(&xs, 2, 0.7).unwrap();
+        assert_eq!(2, got.len());
+        assert_eq!(1719400013, got[0].at);
+        assert!((got[0].value - 143.89349056808817).abs() < 1e-9);
+        assert!((got[1].value - 156.352258358894).abs() < 1e-9);
+
+        for volume_factor in [-0.1, f32::NAN, f32::INFINITY] {
+            assert!(matches!(
+                super::t3(&xs, 2, volume_factor),
+                Err(Error::MustBePositiveF32 { .. })
+            ));
+        }
+    }
+}
@@ -0,0 +1,417 @@
+//! Terminal rendering of charts
+//!
+//! Candlesticks and histograms are drawn with box and block characters, lines
+//! and dots with braille characters which have 2x4 dots per cell. ANSI colours
+//! are optional so that the output can also be written into logs.
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Print candlesticks with 2SMA over them in 60 columns and 12 rows
+//! let sma = m4rs::sma(&candlesticks, 2).unwrap();
+//! let text = m4rs::Chart::new(&candlesticks)
+//!     .series("sma", &sma)
+//!     .to_terminal(60, 12, false)
+//!     .unwrap();
+//! assert_eq!(12, text.lines().count());
+//! println!("{}", text);
+//! ```
+
+use crate::{chart::Panel, svg::label, Chart, Error, PlotStyle};
+
+/// ANSI colour codes of series
+const COLORS: [&str; 6] = ["34", "33", "36", "35", "94", "93"];
+const UP_COLOR: &str = "32";
+const DOWN_COLOR: &str = "31";
+const GRID_COLOR: &str = "90";
+
+/// Columns reserved for axis labels on the right
+const AXIS_WIDTH: usize = 10;
+
+impl Chart {
+    /// Renders the chart into lines of text fitting given columns and rows,
+    /// with ANSI colour escape sequences if `ansi` is true
+    pub fn to_terminal(&self, columns: usize, rows: usize, ansi: bool) -> Result<String, Error> {
+        let layout = self.layout()?;
+        let plot_width = columns.saturating_sub(AXIS_WIDTH).max(1);
+        let mut canvas = Canvas::new(plot_width + AXIS_WIDTH, rows.max(1));
+
+        let n = layout.ats.len().max(1);
+        let x = Scale {
+            n,
+            width: plot_width,
+        };
+
+        let heights = layout.heights(rows as f64);
+        let mut offset: f64 = 0.0;
+        for (panel, h) in layout.panels.iter().zip(heights) {
+            let top = offset.round() as usize;
+            offset += h;
+            let bottom = (offset.round() as usize).min(rows);
+            if bottom <= top {
+                continue;
+            }
+            let area = Area {
+                top,
+                height: bottom - top,
+                width: plot_width,
+                min: panel.min,
+                max: panel.max,
+            };
+            render_panel(&mut canvas, panel, &area, &x);
+        }
+
+        Ok(canvas.render(ansi))
+    }
+}
+
+/// Maps x index to columns and braille dots
+struct Scale {
+    n: usize,
+    width: usize,
+}
+
+impl Scale {
+    fn col(&self, i: usize) -> usize {
+        ((i as f64 + 0.5) * self.width as f64 / self.n as f64) as usize
+    }
+
+    fn dot(&self, i: usize) -> usize {
+        ((i as f64 + 0.5) * (self.width * 2) as f64 / self.n as f64) as usize
+    }
+}
+
+/// Rows of a panel and its value range
+struct Area {
+    top: usize,
+    height: usize,
+    width: usize,
+    min: f64,
+    max: f64,
+}
+
+impl Area {
+    /// Position from the top of the panel in rows
+    fn offset(&self, value: f64) -> f64 {
+        let ratio = (self.max - value) / (self.max - self.min);
+        (ratio * self.height as f64).clamp(0.0, self.height as f64 - 1e-9)
+    }
+
+    fn row(&self, value: f64) -> usize {
+        self.top + self.offset(value) as usize
+    }
+
+    fn dot(&self, value: f64) -> usize {
+        self.top * 4 + (self.offset(value) * 4.0) as usize
+    }
+}
+
+fn render_panel(canvas: &mut Canvas, panel: &Panel, area: &Area, x: &Scale) {
+    let bottom = area.top + area.height - 1;
+    let axis = area.width + 1;
+    for level in &panel.levels {
+        let row = area.row(*level);
+        for col in 0..area.width {
+            canvas.set(col, row, '┄', Some(GRID_COLOR), false);
+        }
+        canvas.text(axis, row, &label(*level), GRID_COLOR);
+    }
+    // labels of the range win over levels on the same row
+    let width = AXIS_WIDTH - 1;
+    canvas.text(
+        axis,
+        area.top,
+        &format!("{:<width$}", label(area.max)),
+        GRID_COLOR,
+    );
+    if bottom > area.top {
+        canvas.text(
+            axis,
+            bottom,
+            &format!("{:<width$}", label(area.min)),
+            GRID_COLOR,
+        );
+    }
+
+    for (a, b) in &panel.fills {
+        let (a, b) = (&panel.series[*a], &panel.series[*b]);
+        let color = COLORS[a.color % COLORS.len()];
+        for (i, (va, vb)) in a.values.iter().zip(&b.values).enumerate() {
+            if let (Some(va), Some(vb)) = (va, vb) {
+                let (from, to) = (area.row(va.max(*vb)), area.row(va.min(*vb)));
+                for row in from..=to {
+                    canvas.set(x.col(i), row, '░', Some(color), false);
+                }
+            }
+        }
+    }
+
+    // candlesticks sharing a column are merged
+    let mut merged: Vec<Option<(f64, f64, f64, f64)>> = vec![None; area.width];
+    for (i, c) in panel.candles.iter().enumerate() {
+        let Some(c) = c else { continue };
+        let cell = &mut merged[x.col(i).min(area.width - 1)];
+        *cell = Some(match cell {
+            None => (c.open, c.high, c.low, c.close),
+            Some((o, h, l, _)) => (*o, h.max(c.high), l.min(c.low), c.close),
+        });
+    }
+    for (col, c) in merged.iter().enumerate() {
+        let Some((open, high, low, close)) = *c else {
+            continue;
+        };
+        let color = if close >= open { UP_COLOR } else { DOWN_COLOR };
+        for row in area.row(high)..=area.row(low) {
+            canvas.set(col, row, '│', Some(color), false);
+        }
+        for row in area.row(open.max(close))..=area.row(open.min(close)) {
+            canvas.set(col, row, '█', Some(color), true);
+        }
+    }
+
+    for series in &panel.series {
+        let color = COLORS[series.color % COLORS.len()];
+        let points = series
+            .values
+            .iter()
+            .enumerate()
+            .filter_map(|(i, v)| Some((i, (*v)?)));
+        match series.style {
+            PlotStyle::Line | PlotStyle::Candlestick => {
+                let mut last: Option<(usize, usize, usize)> = None;
+                for (i, v) in points {
+                    let p = (x.dot(i), area.dot(v));
+                    match last {
+                        Some((j, px, py)) if j + 1 == i => canvas.line((px, py), p, color),
+                        _ => canvas.dot(p.0, p.1, color),
+                    }
+                    last = Some((i, p.0, p.1));
+                }
+            }
+            PlotStyle::Dots => {
+                for (i, v) in points {
+                    canvas.dot(x.dot(i), area.dot(v), color);
+                }
+            }
+            PlotStyle::Histogram => {
+                let zero = area.row(0.0);
+                for (i, v) in points {
+                    let row = area.row(v);
+                    let color = if v >= 0.0 { UP_COLOR } else { DOWN_COLOR };
+                    for row in row.min(zero)..=row.max(zero) {
+                        canvas.set(x.col(i), row, '█', Some(color), true);
+                    }
+                }
+            }
+            PlotStyle::MarkerAbove => {
+                for (i, v) in points {
+                    let row = area.row(v).saturating_sub(1).max(area.top);
+                    canvas.set(x.col(i), row, '▲', Some(color), true);
+                }
+            }
+            PlotStyle::MarkerBelow => {
+                for (i, v) in points {
+                    let row = (area.row(v) + 1).min(bottom);
+                    canvas.set(x.col(i), row, '▼', Some(color), true);
+                }
+            }
+        }
+    }
+
+    let mut col = 0;
+    let titles = std::iter::once((panel.title.as_str(), GRID_COLOR)).chain(
+        panel
+            .series
+            .iter()
+            .filter(|x| panel.title.is_empty() || x.name != panel.title)
+            .map(|x| (x.name.as_str(), COLORS[x.color % COLORS.len()])),
+    );
+    for (text, color) in titles.filter(|(x, _)| !x.is_empty()) {
+        let len = text.chars().count();
+        if col + len > area.width {
+            break;
+        }
+        canvas.text(col, area.top, text, color);
+        col += len + 2;
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Cell {
+    ch: char,
+    color: Option<&'static str>,
+    /// Drawn over braille dots if true
+    solid: bool,
+}
+
+/// Grid of cells with a layer of braille dots over it
+struct Canvas {
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+    dots: Vec<(u8, Option<&'static str>)>,
+}
+
+/// Bits of braille dots by row and column within a cell
+const BRAILLE: [[u8; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+impl Canvas {
+    fn new(width: usize, height: usize) -> Canvas {
+        let blank = Cell {
+            ch: ' ',
+            color: None,
+            solid: false,
+        };
+        Canvas {
+            width,
+            height,
+            cells: vec![blank; width * height],
+            dots: vec![(0, None); width * height],
+        }
+    }
+
+    fn set(&mut self, col: usize, row: usize, ch: char, color: Option<&'static str>, solid: bool) {
+        if col < self.width && row < self.height {
+            self.cells[row * self.width + col] = Cell { ch, color, solid };
+        }
+    }
+
+    fn text(&mut self, col: usize, row: usize, s: &str, color: &'static str) {
+        for (i, ch) in s.chars().enumerate() {
+            self.set(col + i, row, ch, Some(color), true);
+        }
+    }
+
+    /// Sets a braille dot, `x` and `y` counting dots from the top left
+    fn dot(&mut self, x: usize, y: usize, color: &'static str) {
+        let (col, row) = (x / 2, y / 4);
+        if col < self.width && row < self.height {
+            let dot = &mut self.dots[row * self.width + col];
+            dot.0 |= BRAILLE[y % 4][x % 2];
+            dot.1 = Some(color);
+        }
+    }
+
+    fn line(&mut self, from: (usize, usize), to: (usize, usize), color: &'static str) {
+        let (x0, y0) = (from.0 as i64, from.1 as i64);
+        let (x1, y1) = (to.0 as i64, to.1 as i64);
+        let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+        let (sx, sy) = ((x1 - x0).signum(), (y1 - y0).signum());
+        let (mut x, mut y, mut err) = (x0, y0, dx + dy);
+        loop {
+            self.dot(x as usize, y as usize, color);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let e2 = err * 2;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
+
+    fn render(&self, ansi: bool) -> String {
+        let mut ret = String::new();
+        for row in 0..self.height {
+            let cells: Vec<(char, Option<&str>)> = (0..self.width)
+                .map(|col| {
+                    let i = row * self.width + col;
+                    let (cell, (bits, color)) = (self.cells[i], self.dots[i]);
+                    if bits != 0 && !cell.solid {
+                        (char::from_u32(0x2800 + bits as u32).unwrap(), color)
+                    } else {
+                        (cell.ch, cell.color)
+                    }
+                })
+                .collect();
+            let len = cells
+                .iter()
+                .rposition(|(ch, _)| *ch != ' ')
+                .map_or(0, |x| x + 1);
+
+            let mut current = None;
+            for (ch, color) in &cells[..len] {
+                if ansi && *ch != ' ' && *color != current {
+                    match color {
+                        Some(color) => ret.push_str(&format!("\x1b[{}m", color)),
+                        None => ret.push_str("\x1b[0m"),
+                    }
+                    current = *color;
+                }
+                ret.push(*ch);
+            }
+            if current.is_some() {
+                ret.push_str("\x1b[0m");
+            }
+            ret.push('\n');
+        }
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Candlestick;
+
+    use super::*;
+
+    #[test]
+    fn test_to_terminal() {
+        let xs = vec![
+            Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+            Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+            Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+            Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+        ];
+        let chart = Chart::new(&xs)
+            .series("close", &xs)
+            .indicator("rsi(2)".parse().unwrap());
+        let got = chart.to_terminal(30, 12, false).unwrap();
+        assert_eq!(
+            got,
+            [
+                "close │   │          143.5",
+                "  │  ⢀█⡀  █   │",
+                "  │ ⡔⠁█⠈⠑⠢█   █",
+                "  █⠊  █   ⠈⢢  █",
+                "  █   │     ⠱⡀█",
+                "  █   │      ⠘█   │",
+                "  │           │⠉⠢⣀█",
+                "              │   █",
+                "                  │  66.5",
+                "rsi(2)┄┄┄┄┄┄┄┄┄┄┄┄┄┄ 100",
+                "",
+                "┄┄┄┄┄┄┄┄┄┄┄┄┄┄⠒⠒⠤⠤⠄┄ 0",
+                "",
+            ]
+            .join("\n")
+        );
+
+        let got = chart.to_terminal(30, 12, true).unwrap();
+        assert!(got.contains("\x1b[32m"));
+        assert!(got
+            .lines()
+            .all(|x| !x.contains('\x1b') || x.ends_with("\x1b[0m")));
+    }
+
+    #[test]
+    fn test_braille() {
+        let mut canvas = Canvas::new(2, 1);
+        canvas.line((0, 0), (3, 3), "34");
+        assert_eq!("⠑⢄\n", canvas.render(false));
+    }
+}
@@ -0,0 +1,57 @@
+//! TRIX (Triple Exponential Average)
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get 18 bars TRIX calculation result
+//! let result = m4rs::trix(&candlesticks, 18);
+//! ```
+
+use crate::{ema, roc, Error, IndexEntry, IndexEntryLike};
+
+/// Returns TRIX, rate of change in percent of triple smoothed EMA, for given IndexEntry list
+pub fn trix(entries: &[impl IndexEntryLike], duration: usize) -> Result<Vec<IndexEntry>, Error> {
+    let ema1 = ema(entries, duration)?;
+    let ema2 = ema(&ema1, duration)?;
+    let ema3 = ema(&ema2, duration)?;
+    roc(&ema3, 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IndexEntry;
+
+    #[test]
+    fn test_trix() {
+        let xs = vec![
+            IndexEntry::new(1719400001, 110.0),
+            IndexEntry::new(1719400002, 130.0),
+            IndexEntry::new(1719400003, 120.0),
+            IndexEntry::new(1719400004, 95.0),
+            IndexEntry::new(1719400005, 82.0),
+            IndexEntry::new(1719400006, 115.0),
+            IndexEntry::new(1719400007, 145.0),
+            IndexEntry::new(1719400008, 155.0),
+            IndexEntry::new(1719400009, 150.0),
+            IndexEntry::new(1719400010, 160.0),
+            IndexEntry::new(1719400011, 140.0),
+            IndexEntry::new(1719400012, 135.0),
+            IndexEntry::new(1719400013, 150.0),
+            IndexEntry::new(1719400014, 165.0),
+        ];
+        let got = super::trix(&xs, 2).unwrap();
+        assert_eq!(7, got.len());
+        assert_eq!(1719400008, got[0].at);
+        assert!((got[0].value - 14.001877844616315).abs() < 1e-9);
+        assert!((got[3].value - -0.4150775007788042).abs() < 1e-9);
+        assert!((got[6].value - 4.457476253349993).abs() < 1e-9);
+    }
+}
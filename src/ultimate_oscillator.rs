@@ -0,0 +1,133 @@
+//! Ultimate Oscillator
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get Ultimate Oscillator of 7, 14 and 28 bars
+//! let result = m4rs::ultimate_oscillator(&candlesticks, 7, 14, 28);
+//! ```
+
+use crate::{Candlestick, Error, IndexEntry};
+
+/// Returns Ultimate Oscillator for given Candlestick list
+///
+/// Ratios of buying pressure to true range summed over three durations,
+/// weighted 4:2:1 from the shortest, ranging from 0 to 100.
+/// Bars where any of the durations has no true range, such as flat bars, have no entry.
+pub fn ultimate_oscillator(
+    entries: &[Candlestick],
+    short_duration: usize,
+    middle_duration: usize,
+    long_duration: usize,
+) -> Result<Vec<IndexEntry>, Error> {
+    for (short_duration, long_duration) in [
+        (short_duration, middle_duration),
+        (middle_duration, long_duration),
+    ] {
+        if long_duration < short_duration {
+            return Err(Error::LongDurationIsNotGreaterThanShortDuration {
+                short_duration,
+                long_duration,
+            });
+        }
+    }
+    if short_duration == 0 || entries.len() <= long_duration {
+        return Ok(vec![]);
+    }
+    Candlestick::validate_list(entries)?;
+
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.at);
+
+    // buying pressure and true range
+    let calcs: Vec<(u64, f64, f64)> = sorted
+        .windows(2)
+        .map(|xs| {
+            let (prev, cur) = (&xs[0], &xs[1]);
+            let low = cur.low.min(prev.close);
+            (cur.at, cur.close - low, cur.high.max(prev.close) - low)
+        })
+        .collect();
+    Ok(calcs
+        .windows(long_duration)
+        .filter_map(|xs| {
+            let average = |n: usize| {
+                let (bp, tr) = xs
+                    .iter()
+                    .skip(long_duration - n)
+                    .fold((0.0, 0.0), |z, x| (z.0 + x.1, z.1 + x.2));
+                (tr != 0.0).then(|| bp / tr)
+            };
+            Some(IndexEntry {
+                at: xs.last().unwrap().0,
+                value: (average(short_duration)? * 4.0
+                    + average(middle_duration)? * 2.0
+                    + average(long_duration)?)
+                    / 7.0
+                    * 100.0,
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Candlestick, Error};
+
+    #[test]
+    fn test_ultimate_oscillator() {
+        let xs = vec![
+            Candlestick::new(1719400001, 110.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 130.0, 140.0, 100.0, 130.0, 1000.0),
+            Candlestick::new(1719400003, 120.0, 135.0, 120.0, 120.0, 1000.0),
+            Candlestick::new(1719400004, 95.0, 130.0, 80.0, 95.0, 1000.0),
+            Candlestick::new(1719400005, 82.0, 100.0, 70.0, 82.0, 1000.0),
+            Candlestick::new(1719400006, 115.0, 120.0, 80.0, 115.0, 1000.0),
+            Candlestick::new(1719400007, 145.0, 150.0, 110.0, 145.0, 1000.0),
+            Candlestick::new(1719400008, 155.0, 160.0, 140.0, 155.0, 1000.0),
+            Candlestick::new(1719400009, 150.0, 158.0, 145.0, 150.0, 1000.0),
+            Candlestick::new(1719400010, 160.0, 165.0, 148.0, 160.0, 1000.0),
+            Candlestick::new(1719400011, 140.0, 162.0, 135.0, 140.0, 1000.0),
+            Candlestick::new(1719400012, 135.0, 145.0, 128.0, 135.0, 1000.0),
+        ];
+        let got = super::ultimate_oscillator(&xs, 2, 3, 4).unwrap();
+        assert_eq!(8, got.len());
+        assert_eq!(1719400005, got[0].at);
+        assert!((got[0].value - 33.43776106934002).abs() < 1e-9);
+        assert!((got[3].value - 82.56410256410255).abs() < 1e-9);
+        assert!((got[7].value - 32.424088981466035).abs() < 1e-9);
+
+        assert!(matches!(
+            super::ultimate_oscillator(&xs, 2, 4, 3),
+            Err(Error::LongDurationIsNotGreaterThanShortDuration { .. })
+        ));
+    }
+
+    #[test]
+    fn test_ultimate_oscillator_flat() {
+        // flat bars filled in gaps
+        let xs = vec![
+            Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 110.0, 110.0, 110.0, 110.0, 0.0),
+            Candlestick::new(1719400003, 110.0, 110.0, 110.0, 110.0, 0.0),
+            Candlestick::new(1719400004, 110.0, 130.0, 90.0, 120.0, 1000.0),
+            Candlestick::new(1719400005, 120.0, 140.0, 100.0, 130.0, 1000.0),
+            Candlestick::new(1719400006, 130.0, 130.0, 130.0, 130.0, 0.0),
+            Candlestick::new(1719400007, 130.0, 150.0, 120.0, 125.0, 1000.0),
+        ];
+        let got = super::ultimate_oscillator(&xs, 1, 2, 3).unwrap();
+        assert_eq!(3, got.len());
+        assert_eq!((1719400004, 75.0), (got[0].at, got[0].value));
+        assert_eq!((1719400005, 75.0), (got[1].at, got[1].value));
+        assert_eq!(1719400007, got[2].at);
+        assert!((got[2].value - 21.428571428571427).abs() < 1e-9);
+    }
+}
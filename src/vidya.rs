@@ -0,0 +1,76 @@
+//! VIDYA (Variable Index Dynamic Average)
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get 14VIDYA calculation result
+//! let result = m4rs::vidya(&candlesticks, 14);
+//! ```
+
+use crate::{cmo, Error, IndexEntry, IndexEntryLike};
+
+/// Returns VIDYA (Variable Index Dynamic Average) for given IndexEntry list
+///
+/// EMA whose smoothing is scaled by the absolute Chande Momentum Oscillator over `duration`.
+pub fn vidya(entries: &[impl IndexEntryLike], duration: usize) -> Result<Vec<IndexEntry>, Error> {
+    if duration == 0 || entries.len() <= duration {
+        return Ok(vec![]);
+    }
+    IndexEntry::validate_list(entries)?;
+
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.get_at());
+
+    let alpha = 2.0 / (duration as f64 + 1.0);
+    let first = sorted
+        .iter()
+        .take(duration)
+        .fold(0.0, |z, x| z + x.get_value())
+        / duration as f64;
+    Ok(sorted
+        .iter()
+        .skip(duration)
+        .zip(cmo(&sorted, duration)?)
+        .scan(first, |z, (x, c)| {
+            let k = alpha * c.value.abs() / 100.0;
+            *z = k * x.get_value() + (1.0 - k) * *z;
+            Some(IndexEntry {
+                at: x.get_at(),
+                value: *z,
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IndexEntry;
+
+    #[test]
+    fn test_vidya() {
+        let xs = vec![
+            IndexEntry::new(1719400001, 110.0),
+            IndexEntry::new(1719400002, 130.0),
+            IndexEntry::new(1719400003, 120.0),
+            IndexEntry::new(1719400004, 95.0),
+            IndexEntry::new(1719400005, 82.0),
+            IndexEntry::new(1719400006, 115.0),
+            IndexEntry::new(1719400007, 145.0),
+            IndexEntry::new(1719400008, 155.0),
+        ];
+        let got = super::vidya(&xs, 3).unwrap();
+        assert_eq!(5, got.len());
+        assert_eq!(1719400004, got[0].at);
+        assert!((got[0].value - 116.5909090909091).abs() < 1e-9);
+        assert!((got[1].value - 99.29545454545455).abs() < 1e-9);
+        assert!((got[4].value - 134.85046056843453).abs() < 1e-9);
+    }
+}
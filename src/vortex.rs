@@ -0,0 +1,139 @@
+//! Vortex Indicator
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get Vortex Indicator of 14 bars
+//! let result = m4rs::vortex(&candlesticks, 14);
+//! ```
+
+use std::fmt::Display;
+
+use crate::{atr::true_range, Candlestick, Error, IndexEntryLike};
+
+#[derive(Clone, Debug)]
+pub struct VortexEntry {
+    pub at: u64,
+    /// +VI
+    pub plus_vi: f64,
+    /// -VI
+    pub minus_vi: f64,
+}
+
+impl Display for VortexEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Vortex(at={} +vi={} -vi={})",
+            self.at, self.plus_vi, self.minus_vi
+        )
+    }
+}
+
+impl IndexEntryLike for VortexEntry {
+    fn get_at(&self) -> u64 {
+        self.at
+    }
+
+    fn get_value(&self) -> f64 {
+        self.plus_vi - self.minus_vi
+    }
+}
+
+/// Returns Vortex Indicator for given Candlestick list
+///
+/// Windows without true range, such as flat bars, have no entry.
+pub fn vortex(entries: &[Candlestick], duration: usize) -> Result<Vec<VortexEntry>, Error> {
+    if duration == 0 || entries.len() <= duration {
+        return Ok(vec![]);
+    }
+    Candlestick::validate_list(entries)?;
+
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.at);
+
+    // +VM, -VM and true range
+    let calcs: Vec<(u64, f64, f64, f64)> = sorted
+        .windows(2)
+        .zip(true_range(&sorted))
+        .map(|(xs, tr)| {
+            let (prev, cur) = (&xs[0], &xs[1]);
+            (
+                cur.at,
+                (cur.high - prev.low).abs(),
+                (cur.low - prev.high).abs(),
+                tr.value,
+            )
+        })
+        .collect();
+    Ok(calcs
+        .windows(duration)
+        .filter_map(|xs| {
+            let (plus_vm, minus_vm, tr) = xs
+                .iter()
+                .fold((0.0, 0.0, 0.0), |z, x| (z.0 + x.1, z.1 + x.2, z.2 + x.3));
+            if tr == 0.0 {
+                return None;
+            }
+            Some(VortexEntry {
+                at: xs.last().unwrap().0,
+                plus_vi: plus_vm / tr,
+                minus_vi: minus_vm / tr,
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Candlestick;
+
+    #[test]
+    fn test_vortex() {
+        let xs = vec![
+            Candlestick::new(1719400001, 110.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 130.0, 140.0, 100.0, 130.0, 1000.0),
+            Candlestick::new(1719400003, 120.0, 135.0, 120.0, 120.0, 1000.0),
+            Candlestick::new(1719400004, 95.0, 130.0, 80.0, 95.0, 1000.0),
+            Candlestick::new(1719400005, 82.0, 100.0, 70.0, 82.0, 1000.0),
+            Candlestick::new(1719400006, 115.0, 120.0, 80.0, 115.0, 1000.0),
+            Candlestick::new(1719400007, 145.0, 150.0, 110.0, 145.0, 1000.0),
+            Candlestick::new(1719400008, 155.0, 160.0, 140.0, 155.0, 1000.0),
+            Candlestick::new(1719400009, 150.0, 158.0, 145.0, 150.0, 1000.0),
+            Candlestick::new(1719400010, 160.0, 165.0, 148.0, 160.0, 1000.0),
+            Candlestick::new(1719400011, 140.0, 162.0, 135.0, 140.0, 1000.0),
+            Candlestick::new(1719400012, 135.0, 145.0, 128.0, 135.0, 1000.0),
+        ];
+        let got = super::vortex(&xs, 3).unwrap();
+        assert_eq!(9, got.len());
+        assert_eq!(1719400004, got[0].at);
+        assert!((got[0].plus_vi - 0.9047619047619048).abs() < 1e-9);
+        assert_eq!(1.0, got[0].minus_vi);
+        assert_eq!((1.7, 0.4), (got[4].plus_vi, got[4].minus_vi));
+        assert!((got[8].plus_vi - 0.7213114754098361).abs() < 1e-9);
+        assert!((got[8].minus_vi - 1.2131147540983607).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_vortex_flat() {
+        // flat bars filled in gaps
+        let xs = vec![
+            Candlestick::new(1719400001, 110.0, 110.0, 110.0, 110.0, 0.0),
+            Candlestick::new(1719400002, 110.0, 110.0, 110.0, 110.0, 0.0),
+            Candlestick::new(1719400003, 110.0, 110.0, 110.0, 110.0, 0.0),
+            Candlestick::new(1719400004, 110.0, 130.0, 90.0, 120.0, 1000.0),
+        ];
+        let got = super::vortex(&xs, 2).unwrap();
+        assert_eq!(1, got.len());
+        assert_eq!(1719400004, got[0].at);
+        assert_eq!((0.5, 0.5), (got[0].plus_vi, got[0].minus_vi));
+    }
+}
@@ -0,0 +1,202 @@
+//! VWAP (Volume Weighted Average Price)
+//!
+//! Session VWAP resets at the start of each daily session, anchored VWAP accumulates
+//! from a given timestamp. Both use typical price and hold standard deviation of price
+//! weighted by volume to draw bands. Timestamps are seconds since the Unix epoch.
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get VWAP of sessions starting at midnight in UTC+9
+//! let result = m4rs::vwap(&candlesticks, 9 * 60 * 60, 0).unwrap();
+//! let upper = result[4].upper_sigma(2.0);
+//!
+//! // Get VWAP of sessions starting at 17:00 in UTC-5
+//! let result = m4rs::vwap(&candlesticks, -5 * 60 * 60, 17 * 60 * 60);
+//!
+//! // Get VWAP anchored at the 3rd candlestick
+//! let result = m4rs::anchored_vwap(&candlesticks, 1719400003);
+//! ```
+
+use std::fmt::Display;
+
+use crate::{Candlestick, Error, IndexEntryLike};
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+#[derive(Clone, Debug)]
+pub struct VwapEntry {
+    pub at: u64,
+    pub vwap: f64,
+    /// Standard deviation of typical price weighted by volume
+    pub sigma: f64,
+}
+
+impl VwapEntry {
+    pub fn upper_sigma(&self, weight: f32) -> f64 {
+        self.vwap + self.sigma * weight as f64
+    }
+
+    pub fn lower_sigma(&self, weight: f32) -> f64 {
+        self.vwap - self.sigma * weight as f64
+    }
+}
+
+impl Display for VwapEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Vwap(at={} vwap={} sigma={})",
+            self.at, self.vwap, self.sigma
+        )
+    }
+}
+
+impl IndexEntryLike for VwapEntry {
+    fn get_at(&self) -> u64 {
+        self.at
+    }
+
+    fn get_value(&self) -> f64 {
+        self.vwap
+    }
+}
+
+/// Returns session VWAP for given Candlestick list
+///
+/// Sessions start every day at `session_start` seconds from midnight of the time zone
+/// `utc_offset` seconds ahead of UTC.
+pub fn vwap(
+    entries: &[Candlestick],
+    utc_offset: i64,
+    session_start: u32,
+) -> Result<Vec<VwapEntry>, Error> {
+    let session =
+        |at: u64| (at as i64 + utc_offset - session_start as i64).div_euclid(SECONDS_PER_DAY);
+    calc(entries, |prev, x| session(prev.at) != session(x.at))
+}
+
+/// Returns VWAP accumulated from the first Candlestick at or after `anchor`
+pub fn anchored_vwap(entries: &[Candlestick], anchor: u64) -> Result<Vec<VwapEntry>, Error> {
+    let xs: Vec<Candlestick> = entries.iter().filter(|x| x.at >= anchor).cloned().collect();
+    calc(&xs, |_, _| false)
+}
+
+/// Accumulates VWAP, resetting when `reset` returns true for the previous and current Candlestick
+///
+/// Candlesticks before any volume in a session have no entry.
+fn calc(
+    entries: &[Candlestick],
+    reset: impl Fn(&Candlestick, &Candlestick) -> bool,
+) -> Result<Vec<VwapEntry>, Error> {
+    Candlestick::validate_list(entries)?;
+
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.at);
+
+    // sums of volume, price x volume and price^2 x volume
+    let mut sums = (0.0, 0.0, 0.0);
+    let mut prev: Option<&Candlestick> = None;
+    Ok(sorted
+        .iter()
+        .filter_map(|x| {
+            if prev.is_some_and(|prev| reset(prev, x)) {
+                sums = (0.0, 0.0, 0.0);
+            }
+            prev = Some(x);
+
+            let price = x.typical_price();
+            sums.0 += x.volume;
+            sums.1 += price * x.volume;
+            sums.2 += price * price * x.volume;
+            if sums.0 == 0.0 {
+                return None;
+            }
+            let vwap = sums.1 / sums.0;
+            Some(VwapEntry {
+                at: x.at,
+                vwap,
+                sigma: (sums.2 / sums.0 - vwap * vwap).max(0.0).sqrt(),
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Candlestick;
+
+    #[test]
+    fn test_vwap() {
+        // 10:00, 11:00 and 12:00 in UTC on 2024-06-26, where 12:00 is midnight in UTC+12
+        let xs = vec![
+            Candlestick::new(1719396000, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719399600, 110.0, 140.0, 100.0, 120.0, 3000.0),
+            Candlestick::new(1719403200, 130.0, 135.0, 120.0, 121.0, 2000.0),
+        ];
+        let got = super::vwap(&xs, 0, 0).unwrap();
+        assert_eq!(3, got.len());
+        assert_eq!(110.0, got[0].vwap);
+        assert_eq!(0.0, got[0].sigma);
+        assert_eq!(117.5, got[1].vwap);
+        assert!((got[1].sigma - 4.330127018922194).abs() < 1e-9);
+        assert!((got[2].vwap - 120.1111111111111).abs() < 1e-9);
+        assert!((got[2].upper_sigma(2.0) - 130.33574850695842).abs() < 1e-9);
+
+        // session resets at the last one in UTC+12
+        let got = super::vwap(&xs, 12 * 60 * 60, 0).unwrap();
+        assert_eq!(117.5, got[1].vwap);
+        assert_eq!(1719403200, got[2].at);
+        assert_eq!(125.33333333333333, got[2].vwap);
+        assert_eq!(0.0, got[2].sigma);
+
+        // session starting at 11:00 in UTC resets at the 2nd one
+        let got = super::vwap(&xs, 0, 11 * 60 * 60).unwrap();
+        assert_eq!(120.0, got[1].vwap);
+        assert_eq!(0.0, got[1].sigma);
+        assert!((got[2].vwap - 122.13333333333333).abs() < 1e-9);
+        // same boundary as 13:00 in UTC+2
+        let got2 = super::vwap(&xs, 2 * 60 * 60, 13 * 60 * 60).unwrap();
+        assert_eq!(got[2].vwap, got2[2].vwap);
+    }
+
+    #[test]
+    fn test_vwap_without_volume() {
+        // session opens with a bar filled in a gap
+        let xs = vec![
+            Candlestick::new(1719396000, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719482400, 110.0, 110.0, 110.0, 110.0, 0.0),
+            Candlestick::new(1719486000, 110.0, 140.0, 100.0, 120.0, 3000.0),
+        ];
+        let got = super::vwap(&xs, 0, 0).unwrap();
+        assert_eq!(2, got.len());
+        assert_eq!(1719486000, got[1].at);
+        assert_eq!(120.0, got[1].vwap);
+    }
+
+    #[test]
+    fn test_anchored_vwap() {
+        let xs = vec![
+            Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 110.0, 140.0, 100.0, 120.0, 3000.0),
+            Candlestick::new(1719400003, 130.0, 135.0, 120.0, 121.0, 2000.0),
+        ];
+        let got = super::anchored_vwap(&xs, 1719400002).unwrap();
+        assert_eq!(2, got.len());
+        assert_eq!(1719400002, got[0].at);
+        assert_eq!(120.0, got[0].vwap);
+        assert!((got[1].vwap - 122.13333333333333).abs() < 1e-9);
+        assert!((got[1].sigma - 2.6127890589692755).abs() < 1e-9);
+
+        let xs = vec![Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 0.0)];
+        assert!(super::anchored_vwap(&xs, 0).unwrap().is_empty());
+    }
+}
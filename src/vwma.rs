@@ -25,16 +25,16 @@ pub fn vwma(entries: &[Candlestick], duration: usize) -> Result<Vec<IndexEntry>,
     Candlestick::validate_list(entries)?;
 
     let mut sorted = entries.to_owned();
-    sorted.sort_by(|a, b| a.at.cmp(&b.at));
+    sorted.sort_by_key(|x| x.at);
 
     let res: Vec<IndexEntry> = (0..=(sorted.len() - duration))
         .map(|i| sorted.iter().skip(i).take(duration))
-        .map(|xs| {
+        .map(|mut xs| {
             let (cv, v) = xs.clone().fold((0.0, 0.0), |z, x| {
                 (z.0 + x.close * x.volume, z.1 + x.volume)
             });
             IndexEntry {
-                at: xs.last().unwrap().at,
+                at: xs.next_back().unwrap().at,
                 value: if v == 0.0 { f64::NAN } else { cv / v },
             }
         })
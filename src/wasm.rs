@@ -0,0 +1,541 @@
+//! WebAssembly bindings (requires `wasm` feature)
+//!
+//! Every indicator takes timestamps and values as `Float64Array`s and returns
+//! an [`IndicatorSeries`] whose columns are `Float64Array`s too.
+//! Candlestick based indicators take a [`Candles`] object built once from
+//! OHLCV arrays.
+//!
+//! # Examples
+//! ```js
+//! import { Candles, sma, macd, atr } from "m4rs";
+//!
+//! const at = new Float64Array([1719400001, 1719400002, 1719400003]);
+//! const close = new Float64Array([110.0, 130.0, 120.0]);
+//!
+//! // Get 2SMA calculation result
+//! const result = sma(at, close, 2);
+//! result.at; // Float64Array [1719400002, 1719400003]
+//! result.column("value"); // Float64Array [120.0, 125.0]
+//!
+//! // Get MACD calculation result
+//! const m = macd(at, close, 12, 26, 9);
+//! m.names; // ["macd", "signal", "histogram"]
+//!
+//! // Get ATR calculation result
+//! const candles = new Candles(at, open, high, low, close, volume);
+//! const a = atr(candles, 14);
+//! ```
+
+use wasm_bindgen::prelude::*;
+
+use crate::{Candlestick, IchimokuData, IndexEntry, IndexEntryLike};
+
+type Column<'a, T> = (&'a str, &'a dyn Fn(&T) -> f64);
+
+/// Columnar result of an indicator calculation
+#[wasm_bindgen]
+pub struct IndicatorSeries {
+    at: Vec<f64>,
+    names: Vec<String>,
+    columns: Vec<Vec<f64>>,
+}
+
+#[wasm_bindgen]
+impl IndicatorSeries {
+    /// Timestamps of rows
+    #[wasm_bindgen(getter)]
+    pub fn at(&self) -> Vec<f64> {
+        self.at.clone()
+    }
+
+    /// Names of available columns
+    #[wasm_bindgen(getter)]
+    pub fn names(&self) -> Vec<String> {
+        self.names.clone()
+    }
+
+    /// Number of rows
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.at.len()
+    }
+
+    /// Returns values of given column, or `undefined` if there is no such column
+    pub fn column(&self, name: &str) -> Option<Vec<f64>> {
+        self.names
+            .iter()
+            .position(|x| x == name)
+            .map(|i| self.columns[i].clone())
+    }
+}
+
+impl IndicatorSeries {
+    fn from_rows<T>(xs: &[T], at: impl Fn(&T) -> u64, columns: &[Column<T>]) -> Self {
+        IndicatorSeries {
+            at: xs.iter().map(|x| at(x) as f64).collect(),
+            names: columns.iter().map(|(name, _)| name.to_string()).collect(),
+            columns: columns
+                .iter()
+                .map(|(_, f)| xs.iter().map(f).collect())
+                .collect(),
+        }
+    }
+
+    fn from_entries(xs: &[IndexEntry]) -> Self {
+        Self::from_rows(xs, |x| x.at, &[("value", &|x| x.value)])
+    }
+}
+
+/// Candlestick list passed to candlestick based indicators
+#[wasm_bindgen]
+pub struct Candles(Vec<Candlestick>);
+
+#[wasm_bindgen]
+impl Candles {
+    /// Creates Candles from OHLCV arrays of the same length
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        at: &[f64],
+        open: &[f64],
+        high: &[f64],
+        low: &[f64],
+        close: &[f64],
+        volume: &[f64],
+    ) -> Result<Candles, JsError> {
+        let len = at.len();
+        if [open, high, low, close, volume]
+            .iter()
+            .any(|x| x.len() != len)
+        {
+            return Err(JsError::new("all arrays must have the same length"));
+        }
+        Ok(Candles(
+            (0..len)
+                .map(|i| {
+                    Ok(Candlestick::new(
+                        timestamp(at[i])?,
+                        open[i],
+                        high[i],
+                        low[i],
+                        close[i],
+                        volume[i],
+                    ))
+                })
+                .collect::<Result<_, JsError>>()?,
+        ))
+    }
+
+    /// Number of candlesticks
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.0.len()
+    }
+}
+
+fn entries(at: &[f64], values: &[f64]) -> Result<Vec<IndexEntry>, JsError> {
+    if at.len() != values.len() {
+        return Err(JsError::new("at and values must have the same length"));
+    }
+    at.iter()
+        .zip(values)
+        .map(|(at, value)| Ok(IndexEntry::new(timestamp(*at)?, *value)))
+        .collect()
+}
+
+/// Converts JS timestamp to u64, rejecting ones which cannot be represented exactly
+fn timestamp(at: f64) -> Result<u64, JsError> {
+    if !at.is_finite() || at < 0.0 || at.fract() != 0.0 || at >= u64::MAX as f64 {
+        return Err(JsError::new(&format!(
+            "timestamp must be a non-negative integer: {}",
+            at
+        )));
+    }
+    Ok(at as u64)
+}
+
+fn bool_to_f64(x: bool) -> f64 {
+    if x {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+/// Returns SMA (Simple Moving Average)
+#[wasm_bindgen]
+pub fn sma(at: &[f64], values: &[f64], duration: usize) -> Result<IndicatorSeries, JsError> {
+    let xs = crate::sma(&entries(at, values)?, duration)?;
+    Ok(IndicatorSeries::from_entries(&xs))
+}
+
+/// Returns EMA (Exponential Moving Average)
+#[wasm_bindgen]
+pub fn ema(at: &[f64], values: &[f64], duration: usize) -> Result<IndicatorSeries, JsError> {
+    let xs = crate::ema(&entries(at, values)?, duration)?;
+    Ok(IndicatorSeries::from_entries(&xs))
+}
+
+/// Returns WMA (Weighted Moving Average)
+#[wasm_bindgen]
+pub fn wma(at: &[f64], values: &[f64], duration: usize) -> Result<IndicatorSeries, JsError> {
+    let xs = crate::wma(&entries(at, values)?, duration)?;
+    Ok(IndicatorSeries::from_entries(&xs))
+}
+
+/// Returns RMA (Running Moving Average)
+#[wasm_bindgen]
+pub fn rma(at: &[f64], values: &[f64], duration: usize) -> Result<IndicatorSeries, JsError> {
+    let xs = crate::rma(&entries(at, values)?, duration)?;
+    Ok(IndicatorSeries::from_entries(&xs))
+}
+
+/// Returns DEMA (Double Exponential Moving Average)
+#[wasm_bindgen]
+pub fn dema(at: &[f64], values: &[f64], duration: usize) -> Result<IndicatorSeries, JsError> {
+    let xs = crate::dema(&entries(at, values)?, duration)?;
+    Ok(IndicatorSeries::from_entries(&xs))
+}
+
+/// Returns TEMA (Triple Exponential Moving Average)
+#[wasm_bindgen]
+pub fn tema(at: &[f64], values: &[f64], duration: usize) -> Result<IndicatorSeries, JsError> {
+    let xs = crate::tema(&entries(at, values)?, duration)?;
+    Ok(IndicatorSeries::from_entries(&xs))
+}
+
+/// Returns HMA (Hull Moving Average)
+#[wasm_bindgen]
+pub fn hma(at: &[f64], values: &[f64], duration: usize) -> Result<IndicatorSeries, JsError> {
+    let xs = crate::hma(&entries(at, values)?, duration)?;
+    Ok(IndicatorSeries::from_entries(&xs))
+}
+
+/// Returns Momentum
+#[wasm_bindgen]
+pub fn momentum(at: &[f64], values: &[f64], duration: usize) -> Result<IndicatorSeries, JsError> {
+    let xs = crate::momentum(&entries(at, values)?, duration)?;
+    Ok(IndicatorSeries::from_entries(&xs))
+}
+
+/// Returns RCI (Rank Correlation Index)
+#[wasm_bindgen]
+pub fn rci(at: &[f64], values: &[f64], duration: usize) -> Result<IndicatorSeries, JsError> {
+    let xs = crate::rci(&entries(at, values)?, duration)?;
+    Ok(IndicatorSeries::from_entries(&xs))
+}
+
+/// Returns RSI (Relative Strength Index)
+#[wasm_bindgen]
+pub fn rsi(at: &[f64], values: &[f64], duration: usize) -> Result<IndicatorSeries, JsError> {
+    let xs = crate::rsi(&entries(at, values)?, duration)?;
+    Ok(IndicatorSeries::from_entries(&xs))
+}
+
+/// Returns Standard Deviation
+#[wasm_bindgen(js_name = standardDeviation)]
+pub fn standard_deviation(
+    at: &[f64],
+    values: &[f64],
+    duration: usize,
+) -> Result<IndicatorSeries, JsError> {
+    let xs = crate::standard_deviation(&entries(at, values)?, duration)?;
+    Ok(IndicatorSeries::from_entries(&xs))
+}
+
+/// Returns Envelope with columns `basis`, `upper` and `lower`
+#[wasm_bindgen]
+pub fn envelope(at: &[f64], values: &[f64], percent: f32) -> Result<IndicatorSeries, JsError> {
+    let xs = crate::envelope(&entries(at, values)?, percent)?;
+    Ok(IndicatorSeries::from_rows(
+        &xs,
+        |x| x.at,
+        &[
+            ("basis", &|x| x.basis),
+            ("upper", &|x| x.upper),
+            ("lower", &|x| x.lower),
+        ],
+    ))
+}
+
+/// Returns Bolinger Band with columns `avg` and `sigma`
+#[wasm_bindgen(js_name = bolingerBand)]
+pub fn bolinger_band(
+    at: &[f64],
+    values: &[f64],
+    duration: usize,
+) -> Result<IndicatorSeries, JsError> {
+    let xs = crate::bolinger_band(&entries(at, values)?, duration)?;
+    Ok(IndicatorSeries::from_rows(
+        &xs,
+        |x| x.at,
+        &[("avg", &|x| x.avg), ("sigma", &|x| x.sigma)],
+    ))
+}
+
+/// Returns MACD with columns `macd`, `signal` and `histogram`
+#[wasm_bindgen]
+pub fn macd(
+    at: &[f64],
+    values: &[f64],
+    short_duration: usize,
+    long_duration: usize,
+    signal_duration: usize,
+) -> Result<IndicatorSeries, JsError> {
+    let xs = crate::macd(
+        &entries(at, values)?,
+        short_duration,
+        long_duration,
+        signal_duration,
+    )?;
+    Ok(IndicatorSeries::from_rows(
+        &xs,
+        |x| x.at,
+        &[
+            ("macd", &|x| x.macd),
+            ("signal", &|x| x.signal),
+            ("histogram", &|x| x.histogram),
+        ],
+    ))
+}
+
+/// Returns ATR (Average True Range)
+#[wasm_bindgen]
+pub fn atr(candles: &Candles, duration: usize) -> Result<IndicatorSeries, JsError> {
+    let xs = crate::atr(&candles.0, duration)?;
+    Ok(IndicatorSeries::from_entries(&xs))
+}
+
+/// Returns CCI (Commodity Channel Index)
+#[wasm_bindgen]
+pub fn cci(candles: &Candles, duration: usize) -> Result<IndicatorSeries, JsError> {
+    let xs = crate::cci(&candles.0, duration)?;
+    Ok(IndicatorSeries::from_entries(&xs))
+}
+
+/// Returns VWMA (Volume Weighted Moving Average)
+#[wasm_bindgen]
+pub fn vwma(candles: &Candles, duration: usize) -> Result<IndicatorSeries, JsError> {
+    let xs = crate::vwma(&candles.0, duration)?;
+    Ok(IndicatorSeries::from_entries(&xs))
+}
+
+/// Returns Williams %R
+#[wasm_bindgen(js_name = williamsPercentR)]
+pub fn williams_percent_r(candles: &Candles, duration: usize) -> Result<IndicatorSeries, JsError> {
+    let xs = crate::williams_percent_r(&candles.0, duration)?;
+    Ok(IndicatorSeries::from_entries(&xs))
+}
+
+/// Returns Awesome Oscillator
+#[wasm_bindgen(js_name = awesomeOscillator)]
+pub fn awesome_oscillator(
+    candles: &Candles,
+    short_duration: usize,
+    long_duration: usize,
+) -> Result<IndicatorSeries, JsError> {
+    let xs = crate::awesome_oscillator(&candles.0, short_duration, long_duration)?;
+    Ok(IndicatorSeries::from_entries(&xs))
+}
+
+/// Returns Parabolic SAR
+#[wasm_bindgen(js_name = parabolicSar)]
+pub fn parabolic_sar(
+    candles: &Candles,
+    af_init: f32,
+    af_step: f32,
+    af_max: f32,
+) -> Result<IndicatorSeries, JsError> {
+    let xs = crate::parabolic_sar(&candles.0, af_init, af_step, af_max)?;
+    Ok(IndicatorSeries::from_entries(&xs))
+}
+
+/// Returns DMI/ADX with columns `plus_di`, `minus_di`, `dx` and `adx`
+#[wasm_bindgen]
+pub fn dmi(candles: &Candles, duration: usize) -> Result<IndicatorSeries, JsError> {
+    let xs = crate::dmi(&candles.0, duration)?;
+    Ok(IndicatorSeries::from_rows(
+        &xs,
+        |x| x.at,
+        &[
+            ("plus_di", &|x| x.plus_di),
+            ("minus_di", &|x| x.minus_di),
+            ("dx", &|x| x.dx),
+            ("adx", &|x| x.adx),
+        ],
+    ))
+}
+
+/// Returns Stochastics with columns `k` and `d`
+#[wasm_bindgen]
+pub fn stochastics(
+    candles: &Candles,
+    duration_k: usize,
+    duration_d: usize,
+) -> Result<IndicatorSeries, JsError> {
+    let xs = crate::stochastics(&candles.0, duration_k, duration_d)?;
+    Ok(IndicatorSeries::from_rows(
+        &xs,
+        |x| x.at,
+        &[("k", &|x| x.k), ("d", &|x| x.d)],
+    ))
+}
+
+/// Returns Slow Stochastics with columns `k`, `d` and `sd`
+#[wasm_bindgen(js_name = slowStochastics)]
+pub fn slow_stochastics(
+    candles: &Candles,
+    duration_k: usize,
+    duration_d: usize,
+    duration_sd: usize,
+) -> Result<IndicatorSeries, JsError> {
+    let xs = crate::slow_stochastics(&candles.0, duration_k, duration_d, duration_sd)?;
+    Ok(IndicatorSeries::from_rows(
+        &xs,
+        |x| x.at,
+        &[("k", &|x| x.k), ("d", &|x| x.d), ("sd", &|x| x.sd)],
+    ))
+}
+
+/// Returns Williams Fractals with columns `up` and `down` (1 if marked, otherwise 0)
+#[wasm_bindgen(js_name = williamsFractals)]
+pub fn williams_fractals(candles: &Candles, duration: usize) -> Result<IndicatorSeries, JsError> {
+    let xs = crate::williams_fractals::williams_fractals_or_empty(&candles.0, duration)?;
+    Ok(IndicatorSeries::from_rows(
+        &xs,
+        |x| x.at,
+        &[
+            ("up", &|x| bool_to_f64(x.up)),
+            ("down", &|x| bool_to_f64(x.down)),
+        ],
+    ))
+}
+
+/// Returns Heikin Ashi with columns `open`, `high`, `low`, `close` and `volume`
+#[wasm_bindgen(js_name = heikinAshi)]
+pub fn heikin_ashi(candles: &Candles) -> Result<IndicatorSeries, JsError> {
+    let xs = crate::heikin_ashi(&candles.0)?;
+    Ok(IndicatorSeries::from_rows(
+        &xs,
+        |x| x.at,
+        &[
+            ("open", &|x| x.open),
+            ("high", &|x| x.high),
+            ("low", &|x| x.low),
+            ("close", &|x| x.close),
+            ("volume", &|x| x.volume),
+        ],
+    ))
+}
+
+/// Returns Ichimoku Kinkohyo with default parameters
+#[wasm_bindgen(js_name = ichimokuDefault)]
+pub fn ichimoku_default(candles: &Candles) -> Result<IndicatorSeries, JsError> {
+    Ok(ichimoku_series(&crate::ichimoku_default(&candles.0)?))
+}
+
+/// Returns Ichimoku Kinkohyo with columns `conversion_line`, `base_line`,
+/// `leading_span_a`, `leading_span_b` and `lagging_span` (NaN where a line has no value)
+#[wasm_bindgen]
+pub fn ichimoku(
+    candles: &Candles,
+    conversion_line_len: usize,
+    base_line_len: usize,
+    leading_span_b_len: usize,
+    lagging_span: usize,
+) -> Result<IndicatorSeries, JsError> {
+    Ok(ichimoku_series(&crate::ichimoku(
+        &candles.0,
+        conversion_line_len,
+        base_line_len,
+        leading_span_b_len,
+        lagging_span,
+    )?))
+}
+
+fn ichimoku_series(data: &IchimokuData) -> IndicatorSeries {
+    let mut ats: Vec<u64> = [
+        &data.conversion_line,
+        &data.base_line,
+        &data.leading_span_a,
+        &data.leading_span_b,
+        &data.lagging_span,
+    ]
+    .iter()
+    .flat_map(|xs| xs.iter().map(|x| x.get_at()))
+    .collect();
+    ats.sort();
+    ats.dedup();
+    let rows: Vec<_> = ats.iter().filter_map(|at| data.get(*at)).collect();
+    IndicatorSeries::from_rows(
+        &rows,
+        |x| x.at,
+        &[
+            ("conversion_line", &|x| {
+                x.conversion_line.unwrap_or(f64::NAN)
+            }),
+            ("base_line", &|x| x.base_line.unwrap_or(f64::NAN)),
+            ("leading_span_a", &|x| x.leading_span_a.unwrap_or(f64::NAN)),
+            ("leading_span_b", &|x| x.leading_span_b.unwrap_or(f64::NAN)),
+            ("lagging_span", &|x| x.lagging_span.unwrap_or(f64::NAN)),
+        ],
+    )
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    fn test_sma() {
+        let at = [
+            1719400001.0,
+            1719400002.0,
+            1719400003.0,
+            1719400004.0,
+            1719400005.0,
+        ];
+        let close = [110.0, 130.0, 120.0, 95.0, 82.0];
+        let got = sma(&at, &close, 3).unwrap();
+        assert_eq!(vec!["value".to_string()], got.names());
+        assert_eq!(vec![1719400003.0, 1719400004.0, 1719400005.0], got.at());
+        assert_eq!(Some(vec![120.0, 115.0, 99.0]), got.column("value"));
+        assert_eq!(None, got.column("avg"));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_candles() {
+        let at = [1719400001.0, 1719400002.0, 1719400003.0];
+        let open = [100.0, 110.0, 130.0];
+        let high = [130.0, 140.0, 135.0];
+        let low = [90.0, 100.0, 120.0];
+        let close = [110.0, 130.0, 120.0];
+        let volume = [1000.0, 1000.0, 1000.0];
+        let candles = Candles::new(&at, &open, &high, &low, &close, &volume).unwrap();
+        assert_eq!(3, candles.length());
+        assert!(Candles::new(&at, &open, &high, &low, &close, &volume[1..]).is_err());
+
+        let got = heikin_ashi(&candles).unwrap();
+        assert_eq!(3, got.length());
+        assert_eq!(Some(vec![100.0, 105.0, 112.5]), got.column("open"));
+    }
+    #[wasm_bindgen_test]
+    fn test_invalid_timestamps() {
+        let close = [110.0, 130.0];
+        assert!(sma(&[1719400001.0, f64::NAN], &close, 1).is_err());
+        assert!(sma(&[1719400001.0, -1.0], &close, 1).is_err());
+        assert!(sma(&[1719400001.0, 1719400001.5], &close, 1).is_err());
+
+        let prices = [100.0, 110.0];
+        assert!(Candles::new(
+            &[1719400001.0, f64::INFINITY],
+            &prices,
+            &prices,
+            &prices,
+            &prices,
+            &prices
+        )
+        .is_err());
+    }
+}
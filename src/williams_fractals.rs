@@ -13,6 +13,9 @@
 //!
 //! // Get Fractals calculation result
 //! let result = m4rs::williams_fractals(&candlesticks, 2);
+//!
+//! // Get Fractals at the time they are confirmed
+//! let result = m4rs::williams_fractals_causal(&candlesticks, 2);
 //! ```
 
 use std::fmt::Display;
@@ -20,6 +23,7 @@ use std::fmt::Display;
 use crate::{Candlestick, Error};
 
 #[derive(Clone, Debug)]
+#[repr(C)]
 pub struct WilliamsFractalsEntry {
     pub at: u64,
     pub up: bool,
@@ -47,7 +51,7 @@ pub fn williams_fractals(
     Candlestick::validate_list(entries)?;
 
     let mut sorted = entries.to_owned();
-    sorted.sort_by(|a, b| a.at.cmp(&b.at));
+    sorted.sort_by_key(|x| x.at);
 
     let take_range = duration * 2 + 1;
     let ret: Vec<WilliamsFractalsEntry> = (0..=(sorted.len() - take_range))
@@ -92,3 +96,87 @@ pub fn williams_fractals(
 
     Ok([ret, rest].concat())
 }
+
+/// Returns Williams Fractals, or no entries when `entries` are fewer than a window of `duration * 2 + 1`
+pub(crate) fn williams_fractals_or_empty(
+    entries: &[Candlestick],
+    duration: usize,
+) -> Result<Vec<WilliamsFractalsEntry>, Error> {
+    if entries.len() < duration * 2 + 1 {
+        return Ok(vec![]);
+    }
+    williams_fractals(entries, duration)
+}
+
+/// Returns Williams Fractals at the time they are confirmed, for given Candlestick list
+///
+/// A fractal is only known `duration` bars after the marked bar, so `at` of each entry
+/// is the bar closing the window instead of the middle one. Use it to avoid lookahead in backtests.
+pub fn williams_fractals_causal(
+    entries: &[Candlestick],
+    duration: usize,
+) -> Result<Vec<WilliamsFractalsEntry>, Error> {
+    let xs = williams_fractals_or_empty(entries, duration)?;
+
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.at);
+
+    // trailing entries of williams_fractals are not confirmed yet and dropped by zip
+    Ok(xs
+        .iter()
+        .zip(sorted.iter().skip(duration * 2))
+        .map(|(x, confirmed)| WilliamsFractalsEntry {
+            at: confirmed.at,
+            up: x.up,
+            down: x.down,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Candlestick;
+
+    fn candlesticks() -> Vec<Candlestick> {
+        vec![
+            Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+            Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+            Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+            Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+            Candlestick::new(1719400006, 82.0, 120.0, 80.0, 115.0, 1000.0),
+        ]
+    }
+
+    #[test]
+    fn test_williams_fractals() {
+        let got = super::williams_fractals(&candlesticks(), 1).unwrap();
+        assert_eq!(5, got.len());
+        assert_eq!(1719400002, got[0].at);
+        assert!(got[0].up && !got[0].down);
+        assert!(!got[2].up && !got[2].down);
+        assert_eq!(1719400005, got[3].at);
+        assert!(!got[3].up && got[3].down);
+        // last bar is not known yet
+        assert!(!got[4].up && !got[4].down);
+
+        // too few candlesticks for a window
+        assert!(super::williams_fractals_or_empty(&candlesticks()[..4], 2)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_williams_fractals_causal() {
+        let got = super::williams_fractals_causal(&candlesticks(), 1).unwrap();
+        assert_eq!(4, got.len());
+        assert_eq!(1719400003, got[0].at);
+        assert!(got[0].up && !got[0].down);
+        assert_eq!(1719400006, got[3].at);
+        assert!(!got[3].up && got[3].down);
+
+        assert!(super::williams_fractals_causal(&candlesticks()[..2], 2)
+            .unwrap()
+            .is_empty());
+    }
+}
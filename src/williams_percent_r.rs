@@ -15,36 +15,77 @@
 //! let result = m4rs::williams_percent_r(&candlesticks, 14);
 //! ```
 
-use crate::{Candlestick, Error, IndexEntry};
+use crate::{Error, HighLowCloseLike, IndexEntry};
 
-/// Returns Williams %R for given Candlestick list
+/// Returns Williams %R for given Candlestick or IndexEntry list
 pub fn williams_percent_r(
-    entries: &[Candlestick],
+    entries: &[impl HighLowCloseLike],
     duration: usize,
 ) -> Result<Vec<IndexEntry>, Error> {
     if duration == 0 || entries.len() < duration {
         return Ok(vec![]);
     }
-    Candlestick::validate_list(entries)?;
+    IndexEntry::validate_hlc_list(entries)?;
 
     let mut sorted = entries.to_owned();
-    sorted.sort_by_key(|x| x.at);
+    sorted.sort_by_key(|x| x.get_at());
 
     Ok((0..=sorted.len() - duration)
         .map(|i| {
-            let xs = sorted.iter().skip(i).take(duration);
-            let highest = xs.clone().map(|x| x.high).reduce(|z, x| z.max(x)).unwrap();
-            let lowest = xs.clone().map(|x| x.low).reduce(|z, x| z.min(x)).unwrap();
+            let mut xs = sorted.iter().skip(i).take(duration);
+            let highest = xs
+                .clone()
+                .map(|x| x.get_high())
+                .reduce(|z, x| z.max(x))
+                .unwrap();
+            let lowest = xs
+                .clone()
+                .map(|x| x.get_low())
+                .reduce(|z, x| z.min(x))
+                .unwrap();
             let n = highest - lowest;
-            let last = xs.last().unwrap();
+            let last = xs.next_back().unwrap();
             IndexEntry {
-                at: last.at,
+                at: last.get_at(),
                 value: if n == 0.0 {
                     0.0
                 } else {
-                    ((last.close - highest) / n) * 100.0
+                    ((last.get_close() - highest) / n) * 100.0
                 },
             }
         })
         .collect())
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Candlestick, Error, IndexEntry};
+
+    #[test]
+    fn test_williams_percent_r_of_index_entries() {
+        let xs = vec![
+            IndexEntry::new(1719400001, 110.0),
+            IndexEntry::new(1719400002, 130.0),
+            IndexEntry::new(1719400003, 120.0),
+            IndexEntry::new(1719400004, 95.0),
+            IndexEntry::new(1719400005, 82.0),
+        ];
+        let got = super::williams_percent_r(&xs, 3).unwrap();
+        assert_eq!(3, got.len());
+        assert_eq!(-50.0, got[0].value);
+        assert_eq!(-100.0, got[1].value);
+        assert_eq!(-100.0, got[2].value);
+    }
+
+    #[test]
+    fn test_williams_percent_r_validates_candlesticks() {
+        let xs = vec![
+            Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, f64::NAN, 140.0, 100.0, 130.0, 1000.0),
+        ];
+        let got = super::williams_percent_r(&xs, 2);
+        assert!(
+            matches!(got, Err(Error::ContainsNaN { at: 1719400002, field }) if field == "open")
+        );
+    }
+}
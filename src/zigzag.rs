@@ -0,0 +1,289 @@
+//! ZigZag
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get swings reversing by 5%
+//! let result = m4rs::zigzag(&candlesticks, m4rs::ZigZagThreshold::Percent(5.0));
+//!
+//! // Get swings reversing by 3x 14ATR
+//! let result = m4rs::zigzag(
+//!     &candlesticks,
+//!     m4rs::ZigZagThreshold::Atr {
+//!         duration: 14,
+//!         multiplier: 3.0,
+//!     },
+//! );
+//! ```
+
+use std::fmt::Display;
+
+use crate::{atr, Candlestick, Error, IndexEntryLike};
+
+/// Move of price from the last extreme required to reverse the swing
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ZigZagThreshold {
+    /// Percentage of the extreme price
+    Percent(f32),
+    /// Difference of price
+    Absolute(f32),
+    /// Multiple of ATR at the bar reversing the swing
+    Atr { duration: usize, multiplier: f32 },
+}
+
+#[derive(Clone, Debug)]
+pub struct ZigZagEntry {
+    /// Time of the swing high or low
+    pub at: u64,
+    /// Time of the bar which has moved by the threshold and confirmed the swing
+    pub confirmed_at: u64,
+    pub value: f64,
+    /// True for swing high, false for swing low
+    pub high: bool,
+}
+
+impl Display for ZigZagEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ZigZag(at={} confirmed_at={} value={} high={})",
+            self.at, self.confirmed_at, self.value, self.high,
+        )
+    }
+}
+
+impl IndexEntryLike for ZigZagEntry {
+    fn get_at(&self) -> u64 {
+        self.at
+    }
+
+    fn get_value(&self) -> f64 {
+        self.value
+    }
+}
+
+/// Returns confirmed swing highs and lows alternately for given Candlestick list
+///
+/// A swing high is confirmed when low falls from the highest high since the last swing low
+/// by the threshold, and vice versa. A bar making a new extreme does not confirm the swing
+/// on the same bar, since the order of high and low within it is unknown.
+/// The last swing still in progress is not included.
+pub fn zigzag(
+    entries: &[Candlestick],
+    threshold: ZigZagThreshold,
+) -> Result<Vec<ZigZagEntry>, Error> {
+    if entries.is_empty() {
+        return Ok(vec![]);
+    }
+    Candlestick::validate_list(entries)?;
+
+    let (value, field) = match threshold {
+        ZigZagThreshold::Percent(x) => (x, "percent"),
+        ZigZagThreshold::Absolute(x) => (x, "absolute"),
+        ZigZagThreshold::Atr { multiplier, .. } => (multiplier, "multiplier"),
+    };
+    if !value.is_finite() || value <= 0.0 {
+        return Err(Error::MustBePositiveF32 {
+            value,
+            field: field.to_string(),
+        });
+    }
+    if let ZigZagThreshold::Atr { duration: 0, .. } = threshold {
+        return Err(Error::MustBePositiveF32 {
+            value: 0.0,
+            field: "duration".to_string(),
+        });
+    }
+
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.at);
+
+    let atrs = match threshold {
+        ZigZagThreshold::Atr { duration, .. } => atr(&sorted, duration)?,
+        _ => vec![],
+    };
+    let atr_start = sorted.len() - atrs.len();
+    // minimum move from `extreme` to reverse at i-th bar
+    let width = |i: usize, extreme: f64| match threshold {
+        ZigZagThreshold::Percent(x) => Some(extreme * x as f64 / 100.0),
+        ZigZagThreshold::Absolute(x) => Some(x as f64),
+        ZigZagThreshold::Atr { multiplier, .. } => i
+            .checked_sub(atr_start)
+            .map(|i| atrs[i].value * multiplier as f64),
+    };
+
+    let swing = |(at, value): (u64, f64), confirmed_at: u64, high: bool| ZigZagEntry {
+        at,
+        confirmed_at,
+        value,
+        high,
+    };
+    let mut high = (sorted[0].at, sorted[0].high);
+    let mut low = (sorted[0].at, sorted[0].low);
+    // None until the first swing, true while rising
+    let mut rising: Option<bool> = None;
+    let mut ret = vec![];
+    for (i, x) in sorted.iter().enumerate().skip(1) {
+        let falls = width(i, high.1).is_some_and(|w| high.1 - x.low >= w);
+        let rises = width(i, low.1).is_some_and(|w| x.high - low.1 >= w);
+        match rising {
+            None => {
+                // the earlier extreme is the first swing, or the opposite of the move of this bar
+                let first_high = if falls && rises {
+                    high.0 < low.0 || (high.0 == low.0 && x.high <= high.1)
+                } else {
+                    falls
+                };
+                if first_high {
+                    ret.push(swing(high, x.at, true));
+                    low = (x.at, x.low);
+                    rising = Some(false);
+                } else if rises {
+                    ret.push(swing(low, x.at, false));
+                    high = (x.at, x.high);
+                    rising = Some(true);
+                } else {
+                    if x.high > high.1 {
+                        high = (x.at, x.high);
+                    }
+                    if x.low < low.1 {
+                        low = (x.at, x.low);
+                    }
+                }
+            }
+            Some(true) => {
+                if x.high > high.1 {
+                    high = (x.at, x.high);
+                } else if falls {
+                    ret.push(swing(high, x.at, true));
+                    low = (x.at, x.low);
+                    rising = Some(false);
+                }
+            }
+            Some(false) => {
+                if x.low < low.1 {
+                    low = (x.at, x.low);
+                } else if rises {
+                    ret.push(swing(low, x.at, false));
+                    high = (x.at, x.high);
+                    rising = Some(true);
+                }
+            }
+        }
+    }
+    Ok(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ZigZagThreshold;
+    use crate::{Candlestick, Error};
+
+    fn candlesticks() -> Vec<Candlestick> {
+        vec![
+            Candlestick::new(1719400001, 110.0, 130.0, 90.0, 110.0, 1000.0),
+            Candlestick::new(1719400002, 130.0, 140.0, 100.0, 130.0, 1000.0),
+            Candlestick::new(1719400003, 120.0, 135.0, 120.0, 120.0, 1000.0),
+            Candlestick::new(1719400004, 95.0, 130.0, 80.0, 95.0, 1000.0),
+            Candlestick::new(1719400005, 82.0, 100.0, 70.0, 82.0, 1000.0),
+            Candlestick::new(1719400006, 115.0, 120.0, 80.0, 115.0, 1000.0),
+            Candlestick::new(1719400007, 145.0, 150.0, 110.0, 145.0, 1000.0),
+            Candlestick::new(1719400008, 155.0, 160.0, 140.0, 155.0, 1000.0),
+            Candlestick::new(1719400009, 150.0, 158.0, 145.0, 150.0, 1000.0),
+            Candlestick::new(1719400010, 160.0, 165.0, 148.0, 160.0, 1000.0),
+            Candlestick::new(1719400011, 140.0, 162.0, 135.0, 140.0, 1000.0),
+            Candlestick::new(1719400012, 135.0, 145.0, 128.0, 135.0, 1000.0),
+        ]
+    }
+
+    fn swings(threshold: ZigZagThreshold) -> Vec<(u64, u64, f64, bool)> {
+        super::zigzag(&candlesticks(), threshold)
+            .unwrap()
+            .iter()
+            .map(|x| {
+                (
+                    x.at - 1719400000,
+                    x.confirmed_at - 1719400000,
+                    x.value,
+                    x.high,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_zigzag_percent() {
+        assert_eq!(
+            vec![
+                (1, 2, 90.0, false),
+                (2, 4, 140.0, true),
+                (5, 6, 70.0, false),
+                (10, 12, 165.0, true),
+            ],
+            swings(ZigZagThreshold::Percent(20.0))
+        );
+    }
+
+    #[test]
+    fn test_zigzag_absolute() {
+        assert_eq!(
+            vec![
+                (1, 2, 90.0, false),
+                (2, 4, 140.0, true),
+                (5, 6, 70.0, false),
+                (10, 11, 165.0, true),
+            ],
+            swings(ZigZagThreshold::Absolute(30.0))
+        );
+    }
+
+    #[test]
+    fn test_zigzag_atr() {
+        // ATR is available from the 5th bar
+        assert_eq!(
+            vec![
+                (2, 5, 140.0, true),
+                (5, 6, 70.0, false),
+                (10, 11, 165.0, true)
+            ],
+            swings(ZigZagThreshold::Atr {
+                duration: 3,
+                multiplier: 1.0,
+            })
+        );
+    }
+
+    #[test]
+    fn test_zigzag_invalid_threshold() {
+        let invalid = [
+            ZigZagThreshold::Percent(0.0),
+            ZigZagThreshold::Percent(f32::NAN),
+            ZigZagThreshold::Absolute(f32::INFINITY),
+            ZigZagThreshold::Atr {
+                duration: 3,
+                multiplier: f32::NAN,
+            },
+            ZigZagThreshold::Atr {
+                duration: 0,
+                multiplier: 1.0,
+            },
+        ];
+        for threshold in invalid {
+            assert!(matches!(
+                super::zigzag(&candlesticks(), threshold),
+                Err(Error::MustBePositiveF32 { .. })
+            ));
+        }
+        assert!(super::zigzag(&[], ZigZagThreshold::Percent(5.0))
+            .unwrap()
+            .is_empty());
+    }
+}
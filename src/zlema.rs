@@ -0,0 +1,67 @@
+//! ZLEMA (Zero Lag Exponential Moving Average)
+//!
+//! # Examples
+//! ```rust
+//! // Prepare candlesticks in some way
+//! let candlesticks = vec![
+//!     m4rs::Candlestick::new(1719400001, 100.0, 130.0, 90.0, 110.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400002, 110.0, 140.0, 100.0, 130.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400003, 130.0, 135.0, 120.0, 120.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400004, 120.0, 130.0, 80.0, 95.0, 1000.0),
+//!     m4rs::Candlestick::new(1719400005, 90.0, 100.0, 70.0, 82.0, 1000.0),
+//! ];
+//!
+//! // Get 20ZLEMA calculation result
+//! let result = m4rs::zlema(&candlesticks, 20);
+//! ```
+
+use crate::{ema, Error, IndexEntry, IndexEntryLike};
+
+/// Returns ZLEMA (Zero Lag Exponential Moving Average) for given IndexEntry list
+///
+/// EMA of the value pushed ahead by its change over (duration - 1) / 2 entries.
+pub fn zlema(entries: &[impl IndexEntryLike], duration: usize) -> Result<Vec<IndexEntry>, Error> {
+    if duration == 0 || entries.len() < duration {
+        return Ok(vec![]);
+    }
+    IndexEntry::validate_list(entries)?;
+
+    let mut sorted = entries.to_owned();
+    sorted.sort_by_key(|x| x.get_at());
+
+    let lag = (duration - 1) / 2;
+    let delagged: Vec<IndexEntry> = sorted
+        .iter()
+        .zip(sorted.iter().skip(lag))
+        .map(|(prev, x)| IndexEntry {
+            at: x.get_at(),
+            value: x.get_value() * 2.0 - prev.get_value(),
+        })
+        .collect();
+    ema(&delagged, duration)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IndexEntry;
+
+    #[test]
+    fn test_zlema() {
+        let xs = vec![
+            IndexEntry::new(1719400001, 110.0),
+            IndexEntry::new(1719400002, 130.0),
+            IndexEntry::new(1719400003, 120.0),
+            IndexEntry::new(1719400004, 95.0),
+            IndexEntry::new(1719400005, 82.0),
+            IndexEntry::new(1719400006, 115.0),
+            IndexEntry::new(1719400007, 145.0),
+            IndexEntry::new(1719400008, 155.0),
+        ];
+        let got = super::zlema(&xs, 3).unwrap();
+        assert_eq!(4, got.len());
+        assert_eq!(1719400005, got[0].at);
+        assert_eq!(89.5, got[0].value);
+        assert_eq!(118.75, got[1].value);
+        assert_eq!(155.9375, got[3].value);
+    }
+}